@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::rules::{CardState, MatchRules};
+
+// how long the computer waits before each flip, so the people playing can follow along
+const FLIP_DELAY : f32 = 0.8;
+
+/// How good a computer player's memory is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard
+}
+
+impl Difficulty {
+
+    /// Every difficulty, from easiest to hardest
+    pub fn all() -> [Difficulty; 3] {
+        [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard]
+    }
+
+    /// The chance of forgetting each remembered card at the end of every turn
+    pub fn forget_chance(&self) -> f64 {
+        match self {
+            Difficulty::Easy => {0.5}
+            Difficulty::Normal => {0.2}
+            Difficulty::Hard => {0.0}
+        }
+    }
+
+    /// Read a difficulty from its name, as written by [`Difficulty::get_name`]
+    pub fn parse(name : &str) -> Option<Self> {
+        Self::all().iter().copied().find(|difficulty| difficulty.get_name().eq_ignore_ascii_case(name))
+    }
+
+    /// The name shown in menus
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => {"EASY"}
+            Difficulty::Normal => {"NORMAL"}
+            Difficulty::Hard => {"HARD"}
+        }
+    }
+}
+
+/// A computer player. It only knows what it has seen turned face up, and picks cards through the same [`Game::select_card`] path as a mouse click
+///
+/// [`Game::select_card`]: crate::game::Game::select_card
+pub struct AiPlayer {
+    difficulty : Difficulty,
+    memory : HashMap<usize, String>, // board index to the resource location of the card seen there
+    timer : f32
+}
+
+impl AiPlayer {
+
+    /// Create a computer player with nothing remembered yet
+    pub fn create(difficulty : Difficulty) -> Self {
+        Self {
+            difficulty,
+            memory: HashMap::new(),
+            timer: FLIP_DELAY
+        }
+    }
+
+    /// Remember the card at `index`, if it is showing
+    pub fn observe(&mut self, index : usize, rules : &MatchRules) {
+        if let Some(card) = rules.peek(index) {
+            self.memory.insert(index, card.get_resource_location().to_string());
+        }
+    }
+
+    /// Called once a pair has been resolved. Matched cards are dropped from memory, and others may be forgotten depending on the difficulty
+    pub fn end_turn<R : Rng>(&mut self, rules : &MatchRules, rng : &mut R) {
+        let chance = self.difficulty.forget_chance();
+        self.memory.retain(|index, _| rules.get_state(*index) == Some(CardState::FaceDown) && !rng.gen_bool(chance));
+        self.timer = FLIP_DELAY;
+    }
+
+    /// Count down to the next flip, returning the card to pick once it is time
+    pub fn think<R : Rng>(&mut self, delta : f32, rules : &MatchRules, rng : &mut R) -> Option<usize> {
+        self.timer -= delta;
+        if self.timer > 0.0 {
+            return None
+        }
+        self.timer = FLIP_DELAY;
+        self.choose(rules, rng)
+    }
+
+    /// Pick a card to flip straight away, using what is remembered where it can
+    pub fn choose<R : Rng>(&self, rules : &MatchRules, rng : &mut R) -> Option<usize> {
+        let hidden = (0..rules.card_count()).filter(|i| rules.get_state(*i) == Some(CardState::FaceDown)).collect::<Vec<_>>();
+        let showing = (0..rules.card_count()).find(|i| rules.get_state(*i) == Some(CardState::FaceUp));
+
+        match showing {
+            // second flip: go for the partner of the card showing if it has been seen
+            Some(first) => {
+                let wanted = rules.peek(first).map(|card| card.get_resource_location().to_string());
+                if let Some(index) = hidden.iter().find(|i| self.memory.get(i) == wanted.as_ref()) {
+                    return Some(*index)
+                }
+            }
+            // first flip: start on a pair that has been seen, if there is one
+            None => {
+                for (n, a) in hidden.iter().enumerate() {
+                    if let Some(seen) = self.memory.get(a) {
+                        if hidden[n + 1..].iter().any(|b| self.memory.get(b) == Some(seen)) {
+                            return Some(*a)
+                        }
+                    }
+                }
+            }
+        }
+
+        // otherwise try something new, or anything if every card has been seen
+        let unknown = hidden.iter().copied().filter(|i| !self.memory.contains_key(i)).collect::<Vec<_>>();
+        unknown.choose(rng).or(hidden.choose(rng)).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::entities::card_entity::Card;
+    use crate::resource_location::ResourceLocation;
+
+    fn board(names : &[&str]) -> Vec<Card> {
+        names.iter().map(|name| {
+            let rl = ResourceLocation::new("test", &format!("cards/{}.json", name));
+            Card::create(name.to_string(), rl.clone(), rl, vec![])
+        }).collect()
+    }
+
+    // flip two cards where the computer can see them, then settle the pair
+    fn show(ai : &mut AiPlayer, rules : &mut MatchRules, first : usize, second : usize) {
+        for index in [first, second] {
+            rules.flip(index);
+            ai.observe(index, rules);
+        }
+        rules.resolve();
+        ai.end_turn(rules, &mut StdRng::seed_from_u64(0));
+    }
+
+    #[test]
+    fn completes_a_remembered_pair() {
+        let mut rules = MatchRules::new(board(&["a", "b", "c", "a", "b", "c"]), 2);
+        let mut ai = AiPlayer::create(Difficulty::Hard);
+        show(&mut ai, &mut rules, 3, 1);
+        rules.flip(0);
+        for seed in 0..20 {
+            assert_eq!(ai.choose(&rules, &mut StdRng::seed_from_u64(seed)), Some(3));
+        }
+    }
+
+    #[test]
+    fn starts_on_a_known_pair() {
+        let mut rules = MatchRules::new(board(&["a", "b", "c", "d", "b", "a"]), 2);
+        let mut ai = AiPlayer::create(Difficulty::Hard);
+        show(&mut ai, &mut rules, 0, 1);
+        show(&mut ai, &mut rules, 2, 5);
+        for seed in 0..20 {
+            let first = ai.choose(&rules, &mut StdRng::seed_from_u64(seed));
+            assert!(first == Some(0) || first == Some(5), "started on {:?}", first);
+        }
+    }
+
+    #[test]
+    fn unseen_cards_stay_secret() {
+        // the same card is showing on both boards, only the face down cards are in different places
+        let mut together = MatchRules::new(board(&["a", "a", "b", "b", "c", "c"]), 2);
+        let mut apart = MatchRules::new(board(&["a", "b", "c", "a", "b", "c"]), 2);
+        together.flip(0);
+        apart.flip(0);
+        let ai = AiPlayer::create(Difficulty::Hard);
+        for seed in 0..50 {
+            assert_eq!(ai.choose(&together, &mut StdRng::seed_from_u64(seed)), ai.choose(&apart, &mut StdRng::seed_from_u64(seed)));
+        }
+    }
+
+    #[test]
+    fn hard_never_forgets() {
+        let mut rules = MatchRules::new(board(&["a", "b", "c", "d", "a", "b", "c", "d"]), 2);
+        let mut ai = AiPlayer::create(Difficulty::Hard);
+        show(&mut ai, &mut rules, 0, 1);
+        show(&mut ai, &mut rules, 2, 3);
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            ai.end_turn(&rules, &mut rng);
+        }
+        assert_eq!(ai.memory.len(), 4);
+    }
+
+    #[test]
+    fn matched_cards_are_dropped() {
+        let mut rules = MatchRules::new(board(&["a", "a", "b", "b"]), 2);
+        let mut ai = AiPlayer::create(Difficulty::Hard);
+        show(&mut ai, &mut rules, 0, 1);
+        assert!(ai.memory.is_empty());
+    }
+}
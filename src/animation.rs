@@ -0,0 +1,196 @@
+use sdl2::rect::Rect;
+use crate::assets::Registry;
+use crate::render::AssetData;
+use crate::resource_location::ResourceLocation;
+
+/// What an animation does once it reaches its last frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoopMode {
+    /// Stop on the last frame
+    Once,
+    /// Start again from the first frame
+    Loop,
+    /// Play backwards to the first frame, then forwards again
+    PingPong
+}
+
+impl LoopMode {
+    pub fn parse(val : &str) -> Option<Self> {
+        match val {
+            "once" => {Some(Self::Once)}
+            "loop" => {Some(Self::Loop)}
+            "ping_pong" => {Some(Self::PingPong)}
+            _ => {None}
+        }
+    }
+}
+
+/// One frame of an animation, a part of the sheet shown for a time
+pub struct Frame {
+    pub uv : Rect,
+    pub duration : f32, // in seconds
+    pub texture : Option<ResourceLocation> // cut from this texture instead of the animation's sheet, if set
+}
+
+/// A sprite animation loaded from a `"type" : "animation"` data file, made of frames cut from one texture
+pub struct Animation {
+    texture : ResourceLocation, // The sheet the frames are cut from
+    origin : (i32, i32), // The centre point of every frame
+    frames : Vec<Frame>,
+    loop_mode : LoopMode
+}
+
+impl Animation {
+
+    /// Create a new animation instance
+    pub fn create(texture : ResourceLocation, origin : (i32, i32), frames : Vec<Frame>, loop_mode : LoopMode) -> Self {
+        Self {
+            texture,
+            origin,
+            frames,
+            loop_mode
+        }
+    }
+
+    /// The frame showing `elapsed` seconds after the animation started
+    pub fn frame_at(&self, elapsed : f32) -> &Frame {
+        // the frames played in one go round. Ping pong comes back without showing the end frames twice
+        let last = self.frames.len() - 1;
+        let order = match self.loop_mode {
+            LoopMode::PingPong => {(0..=last).chain((1..last).rev()).collect::<Vec<_>>()}
+            _ => {(0..=last).collect::<Vec<_>>()}
+        };
+        let cycle = order.iter().map(|indx| self.frames[*indx].duration).sum::<f32>();
+        if cycle <= 0.0 || last == 0 {
+            return &self.frames[0]
+        }
+
+        let elapsed = elapsed.max(0.0);
+        let mut remaining = match self.loop_mode {
+            LoopMode::Once if elapsed >= cycle => {return &self.frames[last]}
+            LoopMode::Once => {elapsed}
+            _ => {elapsed % cycle}
+        };
+        for indx in &order {
+            let frame = &self.frames[*indx];
+            if remaining < frame.duration {
+                return frame
+            }
+            remaining -= frame.duration;
+        }
+        // only reached through rounding at the very end of the cycle
+        &self.frames[order[order.len() - 1]]
+    }
+
+    /// The asset data for the frame showing `elapsed` seconds in, to draw like any other texture
+    pub fn asset_data(&self, elapsed : f32) -> AssetData {
+        let frame = self.frame_at(elapsed);
+        AssetData {
+            uv: Some(frame.uv),
+            origin: self.origin,
+            resource_location: frame.texture.clone().unwrap_or_else(|| self.texture.clone()),
+        }
+    }
+}
+
+/// [`AssetData`] that can be animated. It keeps the time since the animation started and picks the frame from the loaded animations when drawn,
+/// showing the still asset data if there is no animation or it hasn't been loaded
+pub struct AnimatedAssetData {
+    animation : Option<ResourceLocation>,
+    still : AssetData,
+    elapsed : f32
+}
+
+impl AnimatedAssetData {
+    pub fn create(animation : Option<ResourceLocation>, still : AssetData) -> Self {
+        Self {
+            animation,
+            still,
+            elapsed: 0.0
+        }
+    }
+
+    /// Change the animation played
+    pub fn set_animation(&mut self, animation : Option<ResourceLocation>) {
+        self.animation = animation;
+    }
+
+    /// Change the asset data shown when there is no animation
+    pub fn set_still(&mut self, still : AssetData) {
+        self.still = still;
+    }
+
+    /// Get the asset data shown when there is no animation
+    pub fn get_still(&self) -> &AssetData {
+        &self.still
+    }
+
+    /// Move the animation on by `delta` seconds
+    pub fn tick(&mut self, delta : f32) {
+        self.elapsed += delta;
+    }
+
+    /// Set how far into the animation it is, a negative time holds the first frame until it reaches 0
+    pub fn set_elapsed(&mut self, elapsed : f32) {
+        self.elapsed = elapsed;
+    }
+
+    /// The asset data to draw this frame
+    pub fn current(&self, animations : &Registry<Animation>) -> AssetData {
+        match self.animation.as_ref().and_then(|rl| animations.get(rl)) {
+            Some(animation) => {animation.asset_data(self.elapsed)}
+            None => {self.still.clone()}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // an animation of frames a second long each, numbered by their x position
+    fn animation(count : i32, loop_mode : LoopMode) -> Animation {
+        let frames = (0..count).map(|i| Frame { uv: Rect::new(i, 0, 1, 1), duration: 1.0, texture: None }).collect();
+        Animation::create(ResourceLocation::new("test", "sheet.png"), (0, 0), frames, loop_mode)
+    }
+
+    // the frame numbers shown at the middle of every second, for `seconds` seconds
+    fn played(animation : &Animation, seconds : i32) -> Vec<i32> {
+        (0..seconds).map(|second| animation.frame_at(second as f32 + 0.5).uv.x()).collect()
+    }
+
+    #[test]
+    fn once_stays_on_the_last_frame() {
+        assert_eq!(played(&animation(3, LoopMode::Once), 6), vec![0, 1, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn loop_wraps() {
+        assert_eq!(played(&animation(3, LoopMode::Loop), 7), vec![0, 1, 2, 0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn ping_pong_reverses() {
+        assert_eq!(played(&animation(3, LoopMode::PingPong), 6), vec![0, 1, 2, 1, 0, 1]);
+    }
+
+    #[test]
+    fn single_frame() {
+        for loop_mode in [LoopMode::Once, LoopMode::Loop, LoopMode::PingPong] {
+            assert_eq!(played(&animation(1, loop_mode), 4), vec![0, 0, 0, 0]);
+        }
+    }
+
+    #[test]
+    fn before_the_start_shows_the_first_frame() {
+        assert_eq!(animation(3, LoopMode::Loop).frame_at(-2.5).uv.x(), 0);
+    }
+
+    #[test]
+    fn frames_can_use_their_own_texture() {
+        let mut animation = animation(2, LoopMode::Loop);
+        animation.frames[0].texture = Some(ResourceLocation::new("test", "still.png"));
+        assert_eq!(animation.asset_data(0.5).resource_location.to_string(), "test:still.png");
+        assert_eq!(animation.asset_data(1.5).resource_location.to_string(), "test:sheet.png");
+    }
+}
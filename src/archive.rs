@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// signatures at the start of each zip record
+const END_OF_DIRECTORY : u32 = 0x06054b50;
+const DIRECTORY_ENTRY : u32 = 0x02014b50;
+const LOCAL_HEADER : u32 = 0x04034b50;
+
+// compression methods
+const STORED : u16 = 0;
+const DEFLATED : u16 = 8;
+
+// where a file's data is, and how it is stored
+struct Entry {
+    method : u16,
+    compressed_size : usize,
+    header_offset : usize
+}
+
+/// A zip archive read into memory. Only what resource packs need is supported: stored and deflated files, without zip64 or encryption
+pub struct ZipArchive {
+    bytes : Vec<u8>,
+    entries : HashMap<String, Entry>
+}
+
+impl ZipArchive {
+
+    /// Open a zip file and read its list of files
+    pub fn open(path : &Path) -> Result<Self, String> {
+        let bytes = fs::read(path).map_err(|e| e.to_string())?;
+        Self::parse(bytes)
+    }
+
+    /// Read the list of files from a zip archive held in memory
+    pub fn parse(bytes : Vec<u8>) -> Result<Self, String> {
+        // the end of directory record is the last thing in the file, followed by a comment of up to 64KB
+        let search_from = bytes.len().saturating_sub(22 + u16::MAX as usize);
+        let end = (search_from..bytes.len().saturating_sub(21)).rev()
+            .find(|i| read_u32(&bytes, *i) == Some(END_OF_DIRECTORY))
+            .ok_or("not a zip archive")?;
+
+        let count = read_u16(&bytes, end + 10).ok_or("truncated archive")? as usize;
+        let mut offset = read_u32(&bytes, end + 16).ok_or("truncated archive")? as usize;
+
+        let mut entries = HashMap::new();
+        for _ in 0..count {
+            if read_u32(&bytes, offset) != Some(DIRECTORY_ENTRY) {
+                return Err(String::from("broken file list"))
+            }
+            let field = |at : usize| read_u16(&bytes, offset + at).ok_or("truncated archive");
+            let method = field(10)?;
+            let name_length = field(28)? as usize;
+            let extra_length = field(30)? as usize;
+            let comment_length = field(32)? as usize;
+            let compressed_size = read_u32(&bytes, offset + 20).ok_or("truncated archive")? as usize;
+            let header_offset = read_u32(&bytes, offset + 42).ok_or("truncated archive")? as usize;
+            let name = bytes.get(offset + 46..offset + 46 + name_length).ok_or("truncated archive")?;
+
+            // folders are listed too, but only files are wanted
+            let name = String::from_utf8_lossy(name).replace("\\", "/");
+            if !name.ends_with("/") {
+                entries.insert(name, Entry { method, compressed_size, header_offset });
+            }
+            offset += 46 + name_length + extra_length + comment_length;
+        }
+
+        Ok(Self { bytes, entries })
+    }
+
+    /// The path of every file in the archive, sorted
+    pub fn file_names(&self) -> Vec<String> {
+        let mut names = self.entries.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Read a file out of the archive
+    pub fn read(&self, name : &str) -> Result<Vec<u8>, String> {
+        let entry = self.entries.get(name).ok_or("not in the archive")?;
+
+        // the data starts after the local header, which has its own copy of the name and extra fields
+        let header = entry.header_offset;
+        if read_u32(&self.bytes, header) != Some(LOCAL_HEADER) {
+            return Err(String::from("broken file header"))
+        }
+        let name_length = read_u16(&self.bytes, header + 26).ok_or("truncated archive")? as usize;
+        let extra_length = read_u16(&self.bytes, header + 28).ok_or("truncated archive")? as usize;
+        let start = header + 30 + name_length + extra_length;
+        let data = self.bytes.get(start..start + entry.compressed_size).ok_or("truncated archive")?;
+
+        match entry.method {
+            STORED => {Ok(data.to_vec())}
+            DEFLATED => {miniz_oxide::inflate::decompress_to_vec(data).map_err(|e| format!("could not be decompressed ({:?})", e.status))}
+            method => {Err(format!("uses compression method {}, which isn't supported", method))}
+        }
+    }
+}
+
+fn read_u16(bytes : &[u8], at : usize) -> Option<u16> {
+    bytes.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(bytes : &[u8], at : usize) -> Option<u32> {
+    bytes.get(at..at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Build a zip archive in memory from (name, contents, deflate) entries. Checksums are left at 0 as they aren't read
+    pub(crate) fn zip(files : &[(&str, &[u8], bool)]) -> Vec<u8> {
+        let mut bytes = vec![];
+        let mut directory = vec![];
+        for (name, contents, deflate) in files {
+            let (method, data) = if *deflate {(DEFLATED, miniz_oxide::deflate::compress_to_vec(contents, 6))} else {(STORED, contents.to_vec())};
+            let header_offset = bytes.len() as u32;
+            // the fields both headers share, from the version needed to the extra field length
+            let mut common = vec![];
+            common.extend(20u16.to_le_bytes());
+            common.extend(0u16.to_le_bytes());
+            common.extend(method.to_le_bytes());
+            common.extend([0; 8]); // modified time and date, checksum
+            common.extend((data.len() as u32).to_le_bytes());
+            common.extend((contents.len() as u32).to_le_bytes());
+            common.extend((name.len() as u16).to_le_bytes());
+            common.extend(0u16.to_le_bytes());
+
+            bytes.extend(LOCAL_HEADER.to_le_bytes());
+            bytes.extend(&common);
+            bytes.extend(name.as_bytes());
+            bytes.extend(&data);
+
+            directory.extend(DIRECTORY_ENTRY.to_le_bytes());
+            directory.extend(20u16.to_le_bytes());
+            directory.extend(&common);
+            directory.extend([0; 10]); // comment length, disk, attributes
+            directory.extend(header_offset.to_le_bytes());
+            directory.extend(name.as_bytes());
+        }
+        let directory_offset = bytes.len() as u32;
+        let directory_size = directory.len() as u32;
+        bytes.extend(directory);
+        bytes.extend(END_OF_DIRECTORY.to_le_bytes());
+        bytes.extend([0; 4]);
+        bytes.extend((files.len() as u16).to_le_bytes());
+        bytes.extend((files.len() as u16).to_le_bytes());
+        bytes.extend(directory_size.to_le_bytes());
+        bytes.extend(directory_offset.to_le_bytes());
+        bytes.extend(0u16.to_le_bytes());
+        bytes
+    }
+
+    const TEXT : &[u8] = b"{\"type\" : \"card\", \"name\" : \"Apple\", \"texture\" : \"memory_game:cards/apple.png\"}";
+
+    #[test]
+    fn stored_files() {
+        let archive = ZipArchive::parse(zip(&[("data/test/cards/apple.json", TEXT, false), ("assets/test/x.png", b"png", false)])).unwrap();
+        assert_eq!(archive.file_names(), vec!["assets/test/x.png", "data/test/cards/apple.json"]);
+        assert_eq!(archive.read("data/test/cards/apple.json").unwrap(), TEXT);
+        assert_eq!(archive.read("assets/test/x.png").unwrap(), b"png");
+    }
+
+    #[test]
+    fn deflated_files() {
+        let big = TEXT.repeat(50);
+        let archive = ZipArchive::parse(zip(&[("data/test/cards/apple.json", &big, true), ("empty.txt", b"", true)])).unwrap();
+        assert_eq!(archive.read("data/test/cards/apple.json").unwrap(), big);
+        assert_eq!(archive.read("empty.txt").unwrap(), b"");
+    }
+
+    #[test]
+    fn folders_and_missing_files() {
+        let archive = ZipArchive::parse(zip(&[("pack/", b"", false), ("pack/data/a.json", TEXT, false)])).unwrap();
+        assert_eq!(archive.file_names(), vec!["pack/data/a.json"]);
+        assert!(archive.read("pack/data/b.json").is_err());
+        assert!(archive.read("pack/").is_err());
+    }
+
+    #[test]
+    fn trailing_comment() {
+        let mut bytes = zip(&[("a.json", TEXT, false)]);
+        let comment = b"made by hand";
+        let length = bytes.len();
+        bytes[length - 2..].copy_from_slice(&(comment.len() as u16).to_le_bytes());
+        bytes.extend(comment);
+        assert_eq!(ZipArchive::parse(bytes).unwrap().read("a.json").unwrap(), TEXT);
+    }
+
+    #[test]
+    fn truncated_archives_are_errors() {
+        let bytes = zip(&[("a.json", TEXT, true), ("b.json", TEXT, false)]);
+        // every cut either fails to open or fails to read, without panicking
+        for length in 0..bytes.len() {
+            if let Ok(archive) = ZipArchive::parse(bytes[..length].to_vec()) {
+                for name in archive.file_names() {
+                    let _ = archive.read(&name);
+                }
+            }
+        }
+        assert!(ZipArchive::parse(vec![]).is_err());
+        assert!(ZipArchive::parse(bytes[..bytes.len() - 1].to_vec()).is_err());
+    }
+
+    #[test]
+    fn corrupt_archives_are_errors() {
+        assert!(ZipArchive::parse(b"not a zip file at all, just some text that goes on for a while".to_vec()).is_err());
+
+        // every byte flipped in turn, which mustn't panic
+        let bytes = zip(&[("a.json", TEXT, true), ("b.json", TEXT, false)]);
+        for at in 0..bytes.len() {
+            let mut broken = bytes.clone();
+            broken[at] ^= 0xff;
+            if let Ok(archive) = ZipArchive::parse(broken) {
+                for name in archive.file_names() {
+                    let _ = archive.read(&name);
+                }
+            }
+        }
+
+        // a deflated file with its data scrambled can't be read
+        let mut broken = zip(&[("a.json", &TEXT.repeat(10), true)]);
+        let data = 30 + "a.json".len();
+        for byte in &mut broken[data..data + 8] {
+            *byte = 0xff;
+        }
+        assert!(ZipArchive::parse(broken).unwrap().read("a.json").is_err());
+
+        // an unknown compression method is refused
+        let mut bzip = zip(&[("a.json", TEXT, false)]);
+        let directory = bzip.len() - 22 - 46 - "a.json".len();
+        bzip[directory + 10] = 12;
+        assert!(ZipArchive::parse(bzip).unwrap().read("a.json").is_err());
+    }
+}
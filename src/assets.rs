@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use log::{debug, info};
+use crate::animation::Animation;
+use crate::deck::Deck;
+use crate::entities::card_entity::Card;
+use crate::loader;
+use crate::loader::{JsonFile, LoadError};
+use crate::packs::{PackFile, ResourcePack};
+use crate::resource_location::ResourceLocation;
+use crate::sound::Sound;
+use crate::tile::Tile;
+
+/// Everything of one kind that has been loaded, looked up by [`ResourceLocation`]
+pub struct Registry<T> {
+    entries : HashMap<ResourceLocation, T>
+}
+
+impl<T> Registry<T> {
+
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new()
+        }
+    }
+
+    /// Add an entry, replacing any already at the same resource location
+    pub fn insert(&mut self, resource_location : ResourceLocation, value : T) {
+        self.entries.insert(resource_location, value);
+    }
+
+    /// Take an entry out, returning it if there was one
+    pub fn remove(&mut self, resource_location : &ResourceLocation) -> Option<T> {
+        self.entries.remove(resource_location)
+    }
+
+    /// Get the entry at a resource location
+    pub fn get(&self, resource_location : &ResourceLocation) -> Option<&T> {
+        self.entries.get(resource_location)
+    }
+
+    /// Get an entry from a resource location written as a string, like the ones kept in save files
+    pub fn find(&self, resource_location : &str) -> Option<&T> {
+        ResourceLocation::try_parse(resource_location).and_then(|rl| self.get(&rl))
+    }
+
+    /// The number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing has been loaded
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Every entry, in no particular order
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.entries.values()
+    }
+
+    /// Every resource location, sorted so the order doesn't depend on how they were loaded
+    pub fn keys(&self) -> Vec<ResourceLocation> {
+        let mut keys = self.entries.keys().cloned().collect::<Vec<_>>();
+        keys.sort_by_key(|rl| rl.to_string());
+        keys
+    }
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads data files with one `"type"` into the [`Assets`]. Loaders are picked by the type written in each file, see [`Assets::loaders`]
+pub trait DataLoader {
+    /// The `"type"` of the files this loader reads
+    fn data_type(&self) -> &str;
+
+    /// Read a file and add what it describes to the assets
+    fn load(&self, file : &JsonFile, resource_location : ResourceLocation, assets : &mut Assets) -> Result<(), LoadError>;
+}
+
+/// A [`DataLoader`] that parses each file into one value and stores it in a registry
+pub struct RegistryLoader<T> {
+    data_type : &'static str,
+    parse : fn(&JsonFile, ResourceLocation) -> Result<T, LoadError>,
+    registry : fn(&mut Assets) -> &mut Registry<T>
+}
+
+impl<T> RegistryLoader<T> {
+    pub fn create(data_type : &'static str, parse : fn(&JsonFile, ResourceLocation) -> Result<T, LoadError>, registry : fn(&mut Assets) -> &mut Registry<T>) -> Box<Self> {
+        Box::new(Self {
+            data_type,
+            parse,
+            registry
+        })
+    }
+}
+
+impl<T> DataLoader for RegistryLoader<T> {
+    fn data_type(&self) -> &str {
+        self.data_type
+    }
+
+    fn load(&self, file : &JsonFile, resource_location : ResourceLocation, assets : &mut Assets) -> Result<(), LoadError> {
+        let value = (self.parse)(file, resource_location.clone())?;
+        (self.registry)(assets).insert(resource_location, value);
+        Ok(())
+    }
+}
+
+/// Everything the game loads from the data and assets directories, apart from textures which belong to the window
+#[derive(Default)]
+pub struct Assets {
+    pub tiles : Registry<Tile>,
+    pub cards : Registry<Card>,
+    pub decks : Registry<Deck>,
+    pub sounds : Registry<Sound>,
+    pub animations : Registry<Animation>
+}
+
+impl Assets {
+
+    /// The loader for every type of data file the game knows about. A new type of data needs a registry above and a loader here
+    pub fn loaders() -> Vec<Box<dyn DataLoader>> {
+        vec![
+            RegistryLoader::create("tile", loader::parse_tile, |assets| &mut assets.tiles),
+            RegistryLoader::create("card", loader::parse_card, |assets| &mut assets.cards),
+            RegistryLoader::create("deck", loader::parse_deck, |assets| &mut assets.decks),
+            RegistryLoader::create("animation", loader::parse_animation, |assets| &mut assets.animations),
+        ]
+    }
+
+    /// Load the data files and sounds from a stack of packs, see [`ResourcePack::stack`]
+    pub fn load(packs : &[ResourcePack], errors : &mut Vec<LoadError>) -> Self {
+        let mut assets = Self::default();
+        assets.load_data(packs, &Self::loaders(), errors);
+        assets.load_sounds(packs, errors);
+        assets
+    }
+
+    /// Load every data file in the packs, handing each to the loader for its `"type"`. Files in later packs replace the ones at the same resource location
+    /// in earlier packs. Files that can't be read are skipped and added to `errors`
+    pub fn load_data(&mut self, packs : &[ResourcePack], loaders : &[Box<dyn DataLoader>], errors : &mut Vec<LoadError>) {
+        info!("Loading data...");
+        let mut counts : HashMap<String, u32> = HashMap::new();
+
+        for pack in packs {
+            for pack_file in pack.data() {
+                match self.load_data_file(pack, &pack_file, loaders) {
+                    Ok(Some(data_type)) => {
+                        info!("Loaded {} : {} from {}", data_type, pack_file.resource_location.to_string(), pack.get_name());
+                        *counts.entry(data_type).or_insert(0) += 1;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {loader::report(errors, e)}
+                }
+            }
+        }
+
+        for data_loader in loaders {
+            info!("{} {}s loaded!", counts.get(data_loader.data_type()).unwrap_or(&0), data_loader.data_type());
+        }
+    }
+
+    /// Load one data file from a pack, returning the type it was loaded as. Files with a type no loader knows about are skipped
+    pub fn load_data_file(&mut self, pack : &ResourcePack, pack_file : &PackFile, loaders : &[Box<dyn DataLoader>]) -> Result<Option<String>, LoadError> {
+        let file = JsonFile::parse(pack_file.path.clone(), pack.read(pack_file)?)?;
+
+        // the ResourceLocation of this JSON file
+        let resource_location = pack_file.resource_location.clone();
+
+        let data_type = file.data_type();
+        let data_loader = match loaders.iter().find(|l| l.data_type() == data_type) {
+            Some(data_loader) => {data_loader}
+            None => {
+                debug!("No loader for {} of type \"{}\"", resource_location.to_string(), data_type);
+                return Ok(None)
+            }
+        };
+        data_loader.load(&file, resource_location, self)?;
+        Ok(Some(data_type))
+    }
+
+    /// Load every sound in the packs
+    pub fn load_sounds(&mut self, packs : &[ResourcePack], errors : &mut Vec<LoadError>) {
+        self.sounds = loader::load_sounds(packs, errors);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::render;
+
+/// The size of a card texture
+pub const CARD_SIZE: (u32, u32) = (45, 68);
+
+/// The gap between cards when there is room for it
+pub const CARD_SPACING: (u32, u32) = (3, 2);
+
+/// Space left free around the board, horizontally for the player widgets and vertically so the cards don't touch the edge of the screen
+pub const BOARD_MARGIN: (u32, u32) = (80, 12);
+
+/// A grid of card slots, centred on the world origin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoardLayout {
+    pub columns : usize,
+    pub rows : usize,
+    // the distance between the centres of neighbouring cards
+    pub step : (f32, f32),
+    count : usize
+}
+
+impl BoardLayout {
+
+    /// Work out the best grid for a number of cards, given the current screen size
+    pub fn for_cards(count : usize) -> Self {
+        let dims = render::get_actual_dimensions().lock().unwrap().get();
+        Self::fit(count, (dims.0.saturating_sub(BOARD_MARGIN.0 * 2), dims.1.saturating_sub(BOARD_MARGIN.1 * 2)))
+    }
+
+    /// Work out the best grid for a number of cards in an area. If the cards can't fit at full spacing they are squeezed
+    /// together until they overlap, picking the grid that needs squeezing the least
+    pub fn fit(count : usize, area : (u32, u32)) -> Self {
+        let full_step = ((CARD_SIZE.0 + CARD_SPACING.0) as f32, (CARD_SIZE.1 + CARD_SPACING.1) as f32);
+        let area_ratio = area.0 as f32 / area.1.max(1) as f32;
+
+        let mut best : Option<(Self, f32, usize, f32)> = None;
+        for columns in 1..=count.max(1) {
+            let rows = count.div_ceil(columns);
+
+            // squeeze the cards together if the grid would be bigger than the area
+            let step = (
+                Self::squeeze(columns, area.0, CARD_SIZE.0, full_step.0),
+                Self::squeeze(rows.max(1), area.1, CARD_SIZE.1, full_step.1)
+            );
+            let squeeze = (step.0 / full_step.0).min(step.1 / full_step.1);
+            let empty = columns * rows - count;
+
+            // how far the shape of the grid is from the shape of the area
+            let extents = ((columns - 1) as f32 * step.0 + CARD_SIZE.0 as f32, (rows.max(1) - 1) as f32 * step.1 + CARD_SIZE.1 as f32);
+            let ratio_error = ((extents.0 / extents.1) / area_ratio).ln().abs();
+
+            let layout = Self { columns, rows, step, count };
+            let better = match &best {
+                None => {true}
+                Some((_, b_squeeze, b_empty, b_ratio)) => {
+                    if (squeeze - b_squeeze).abs() > 0.001 {
+                        squeeze > *b_squeeze
+                    }
+                    else if empty != *b_empty {
+                        empty < *b_empty
+                    }
+                    else {
+                        ratio_error < *b_ratio
+                    }
+                }
+            };
+            if better {
+                best = Some((layout, squeeze, empty, ratio_error))
+            }
+        }
+        best.unwrap().0
+    }
+
+    // the largest step up to `full` that fits `count` cards of `size` into `space`
+    fn squeeze(count : usize, space : u32, size : u32, full : f32) -> f32 {
+        if count <= 1 {
+            return full
+        }
+        let fitting = (space as f32 - size as f32) / (count - 1) as f32;
+        fitting.min(full).max(1.0).floor()
+    }
+
+    /// The world space centre of every slot, filled row by row. A short last row is centred
+    pub fn positions(&self) -> Vec<(f32, f32)> {
+        let mut positions = vec![];
+        let height = (self.rows.max(1) - 1) as f32 * self.step.1;
+        for index in 0..self.count {
+            let row = index / self.columns;
+            let column = index % self.columns;
+            // the number of cards in this row
+            let in_row = self.columns.min(self.count - row * self.columns);
+            let width = (in_row - 1) as f32 * self.step.0;
+            positions.push((
+                (column as f32 * self.step.0 - width / 2.0).floor(),
+                (row as f32 * self.step.1 - height / 2.0).floor()
+            ));
+        }
+        positions
+    }
+
+    /// The part of a card that can be clicked, as cards further down and to the right are drawn over the ones before them
+    pub fn hitbox(&self) -> (u32, u32) {
+        (CARD_SIZE.0.min(self.step.0 as u32), CARD_SIZE.1.min(self.step.1 as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL_STEP : (f32, f32) = ((CARD_SIZE.0 + CARD_SPACING.0) as f32, (CARD_SIZE.1 + CARD_SPACING.1) as f32);
+
+    #[test]
+    fn every_card_gets_a_slot() {
+        for count in [2, 6, 12, 16, 20, 30, 52] {
+            let layout = BoardLayout::fit(count, (400, 300));
+            assert!(layout.columns * layout.rows >= count, "{} cards in {:?}", count, layout);
+            // no row is left completely empty
+            assert!(layout.columns * (layout.rows - 1) < count, "{} cards in {:?}", count, layout);
+            assert_eq!(layout.positions().len(), count);
+        }
+    }
+
+    #[test]
+    fn roomy_area_uses_full_spacing() {
+        let layout = BoardLayout::fit(12, (1000, 1000));
+        assert_eq!(layout.step, FULL_STEP);
+        assert_eq!(layout.hitbox(), CARD_SIZE);
+    }
+
+    #[test]
+    fn follows_the_shape_of_the_area() {
+        let wide = BoardLayout::fit(12, (1000, 200));
+        let tall = BoardLayout::fit(12, (200, 1000));
+        assert!(wide.columns > wide.rows);
+        assert!(tall.rows > tall.columns);
+    }
+
+    #[test]
+    fn tight_area_squeezes_the_cards() {
+        let area = (200, 150);
+        let layout = BoardLayout::fit(40, area);
+        assert!(layout.step.0 < FULL_STEP.0 || layout.step.1 < FULL_STEP.1);
+        // the squeezed grid still fits in the area
+        let extents = ((layout.columns - 1) as f32 * layout.step.0 + CARD_SIZE.0 as f32, (layout.rows - 1) as f32 * layout.step.1 + CARD_SIZE.1 as f32);
+        assert!(extents.0 <= area.0 as f32 && extents.1 <= area.1 as f32, "{:?} in {:?}", extents, area);
+    }
+
+    #[test]
+    fn positions_are_centred() {
+        let layout = BoardLayout::fit(6, (1000, 1000));
+        let positions = layout.positions();
+        let (sum_x, sum_y) = positions.iter().fold((0.0, 0.0), |sum, pos| (sum.0 + pos.0, sum.1 + pos.1));
+        assert!((sum_x / 6.0).abs() <= 1.0 && (sum_y / 6.0).abs() <= 1.0);
+    }
+
+    #[test]
+    fn empty_board() {
+        let layout = BoardLayout::fit(0, (400, 300));
+        assert!(layout.positions().is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+use log::{warn, LevelFilter};
+use crate::utils;
+use crate::widgets::players_widget::MAX_PLAYERS;
+
+const USAGE : &str = "Usage: memory_game [options]
+
+Display:
+  --windowed            Play in a window, whatever the settings say
+  --scale <n>           Draw the game at n times its size
+
+Match:
+  --seed <n>            Deal the board from this seed
+  --daily               Deal today's board, the same for everyone
+  --deck <deck>         Play with this deck, like memory_game:decks/animals.json
+  --players <n>         Number of players, from 1 to 4
+  --replay <file>       Watch a recorded match
+
+Files:
+  --data-dir <dir>      Load data files from here instead of ./data
+  --assets-dir <dir>    Load textures and sounds from here instead of ./assets
+
+Other:
+  --log-level <level>   How much is logged to the console: off, error, warn, info, debug or trace
+  --headless            Play a match between computer players without a window and print the result
+  --dev                 Reload textures, sounds and data files as soon as they are saved
+  --help                Show this message";
+
+/// Options passed on the command line. Anything not given is left to the settings, or the usual default
+pub struct Args {
+    pub windowed : bool,
+    pub scale : Option<u32>,
+    pub seed : Option<u64>,
+    pub deck : Option<String>,
+    pub players : Option<usize>,
+    pub replay : Option<String>,
+    pub data_dir : String,
+    pub assets_dir : String,
+    pub log_level : LevelFilter,
+    pub headless : bool,
+    pub dev : bool,
+    pub problems : Vec<String> // options that couldn't be used, logged once the logger has started
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            windowed: false,
+            scale: None,
+            seed: None,
+            deck: None,
+            players: None,
+            replay: None,
+            data_dir: "./data".to_string(),
+            assets_dir: "./assets".to_string(),
+            log_level: LevelFilter::Info,
+            headless: false,
+            dev: false,
+            problems: vec![]
+        }
+    }
+}
+
+impl Args {
+
+    /// Read the options the game was started with
+    pub fn from_env() -> Self {
+        Self::parse(&std::env::args().skip(1).collect::<Vec<_>>())
+    }
+
+    /// Read options from a list of arguments, not including the program name. `--help` prints the usage and exits.
+    /// This runs before the logger starts, so problems are kept for [`Args::report`]
+    pub fn parse(args : &[String]) -> Self {
+        let mut ret = Self::default();
+        let mut problems = vec![];
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--windowed" => {ret.windowed = true}
+                "--scale" => {ret.scale = value(&mut iter, arg, &mut problems).filter(|scale : &u32| *scale > 0)}
+                "--seed" => {ret.seed = value(&mut iter, arg, &mut problems)}
+                "--daily" => {ret.seed = Some(utils::daily_seed())}
+                "--deck" => {ret.deck = value(&mut iter, arg, &mut problems)}
+                "--players" => {
+                    ret.players = value(&mut iter, arg, &mut problems).map(|players : usize| players.clamp(1, MAX_PLAYERS))
+                }
+                "--replay" => {ret.replay = value(&mut iter, arg, &mut problems)}
+                "--data-dir" => {
+                    if let Some(dir) = value(&mut iter, arg, &mut problems) {ret.data_dir = dir}
+                }
+                "--assets-dir" => {
+                    if let Some(dir) = value(&mut iter, arg, &mut problems) {ret.assets_dir = dir}
+                }
+                "--log-level" => {
+                    if let Some(level) = value(&mut iter, arg, &mut problems) {ret.log_level = level}
+                }
+                "--headless" => {ret.headless = true}
+                "--dev" => {ret.dev = true}
+                "--help" | "-h" => {
+                    println!("{}", USAGE);
+                    std::process::exit(0);
+                }
+                _ => {problems.push(format!("Unknown option {}, see --help", arg))}
+            }
+        }
+        ret.problems = problems;
+        ret
+    }
+
+    /// Log any options that couldn't be used
+    pub fn report(&self) {
+        for problem in &self.problems {
+            warn!("{}", problem);
+        }
+    }
+}
+
+// read the value after an option, noting a problem if it is missing or can't be read
+fn value<'a, T : std::str::FromStr>(iter : &mut impl Iterator<Item = &'a String>, option : &str, problems : &mut Vec<String>) -> Option<T> {
+    match iter.next().map(|value| value.parse::<T>()) {
+        Some(Ok(value)) => {Some(value)}
+        Some(Err(_)) => {problems.push(format!("{} was given a value it can't use", option)); None}
+        None => {problems.push(format!("{} needs a value after it", option)); None}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args : &[&str]) -> Args {
+        Args::parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn no_options_uses_the_defaults() {
+        let args = parse(&[]);
+        assert!(!args.windowed && !args.headless && !args.dev);
+        assert_eq!(args.seed, None);
+        assert_eq!(args.data_dir, "./data");
+        assert_eq!(args.assets_dir, "./assets");
+        assert_eq!(args.log_level, LevelFilter::Info);
+        assert!(args.problems.is_empty());
+    }
+
+    #[test]
+    fn every_option() {
+        let args = parse(&["--windowed", "--scale", "3", "--seed", "1234", "--deck", "memory_game:decks/animals.json", "--players", "3",
+            "--replay", "match.json", "--data-dir", "my_data", "--assets-dir", "my_assets", "--log-level", "debug", "--headless", "--dev"]);
+        assert!(args.windowed);
+        assert_eq!(args.scale, Some(3));
+        assert_eq!(args.seed, Some(1234));
+        assert_eq!(args.deck.as_deref(), Some("memory_game:decks/animals.json"));
+        assert_eq!(args.players, Some(3));
+        assert_eq!(args.replay.as_deref(), Some("match.json"));
+        assert_eq!(args.data_dir, "my_data");
+        assert_eq!(args.assets_dir, "my_assets");
+        assert_eq!(args.log_level, LevelFilter::Debug);
+        assert!(args.headless);
+        assert!(args.dev);
+        assert!(args.problems.is_empty());
+    }
+
+    #[test]
+    fn players_are_clamped() {
+        assert_eq!(parse(&["--players", "0"]).players, Some(1));
+        assert_eq!(parse(&["--players", "9"]).players, Some(MAX_PLAYERS));
+    }
+
+    #[test]
+    fn bad_options_are_noted() {
+        let args = parse(&["--seed", "abc", "--frobnicate", "--scale", "0", "--data-dir"]);
+        assert_eq!(args.seed, None);
+        assert_eq!(args.scale, None);
+        assert_eq!(args.data_dir, "./data");
+        assert_eq!(args.problems, vec![
+            String::from("--seed was given a value it can't use"),
+            String::from("Unknown option --frobnicate, see --help"),
+            String::from("--data-dir needs a value after it")
+        ]);
+    }
+}
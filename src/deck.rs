@@ -0,0 +1,190 @@
+use crate::assets::Registry;
+use log::warn;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::entities::card_entity::Card;
+use crate::resource_location::ResourceLocation;
+
+/// A themed set of cards loaded from a `"type" : "deck"` data file. Cards can be listed one by one, picked up by their tags, or both
+pub struct Deck {
+    name : String, // The name of the deck
+    resource_location : ResourceLocation, // The resource location of the deck data file
+    cards : Vec<ResourceLocation>, // Card data files that are always in the deck
+    tags : Vec<String>, // Any card with one of these tags is in the deck too
+    pairs : Option<u32>, // How many pairs are dealt, if not set every card is used
+    card_back : ResourceLocation // The texture or animation shown on the back of the cards
+}
+
+impl Deck {
+
+    /// Create a new deck instance
+    pub fn create(name : String, resource_location : ResourceLocation, cards : Vec<ResourceLocation>, tags : Vec<String>, pairs : Option<u32>, card_back : ResourceLocation) -> Self {
+        Self {
+            name,
+            resource_location,
+            cards,
+            tags,
+            pairs,
+            card_back
+        }
+    }
+
+    /// Get the deck name
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Get the deck data resource location
+    pub fn get_resource_location(&self) -> ResourceLocation {
+        self.resource_location.clone()
+    }
+
+    /// Get the texture or animation for the back of the cards
+    pub fn get_card_back(&self) -> ResourceLocation {
+        self.card_back.clone()
+    }
+
+    /// Every card that belongs to this deck, sorted by resource location so the order doesn't depend on how they were loaded
+    pub fn pool(&self, cards : &Registry<Card>) -> Vec<Card> {
+        let mut pool = vec![];
+        for rl in &self.cards {
+            match cards.get(rl) {
+                Some(card) => {pool.push(card.clone())}
+                None => {warn!("Deck {} lists card {} which doesn't exist!", self.resource_location.to_string(), rl.to_string())}
+            }
+        }
+        for card in cards.values() {
+            let listed = pool.iter().any(|c : &Card| c.get_resource_location().to_string() == card.get_resource_location().to_string());
+            if !listed && card.get_tags().iter().any(|tag| self.tags.contains(tag)) {
+                pool.push(card.clone())
+            }
+        }
+        pool.sort_by_key(|card| card.get_resource_location().to_string());
+        pool
+    }
+
+    /// The number of pairs a match with this deck uses by default
+    pub fn get_pairs(&self, cards : &Registry<Card>) -> u32 {
+        let available = self.pool(cards).len() as u32;
+        match self.pairs {
+            Some(pairs) => {pairs.min(available)}
+            None => {available}
+        }
+    }
+
+    /// Pick the cards for a match, if the deck has more cards than pairs a random selection is used
+    pub fn pick<R : Rng>(&self, cards : &Registry<Card>, pairs : u32, rng : &mut R) -> Vec<Card> {
+        let mut pool = self.pool(cards);
+        if (pool.len() as u32) < pairs {
+            warn!("Deck {} only has {} cards, but {} pairs were asked for", self.resource_location.to_string(), pool.len(), pairs);
+        }
+        pool.shuffle(rng);
+        pool.truncate(pairs as usize);
+        pool
+    }
+}
+
+/// Lay out the board for a match: two of every card picked from the deck, shuffled. Without a deck every card is used, in order of resource location.
+/// The same deck, pairs and seeded `rng` always give the same board
+pub fn deal<R : Rng>(deck : Option<&Deck>, cards : &Registry<Card>, pairs : Option<u32>, rng : &mut R) -> Vec<Card> {
+    let in_play = match deck {
+        Some(deck) => {deck.pick(cards, pairs.unwrap_or(deck.get_pairs(cards)), rng)}
+        None => {
+            let mut all = cards.values().cloned().collect::<Vec<_>>();
+            all.sort_by_key(|card| card.get_resource_location().to_string());
+            all.truncate(pairs.unwrap_or(all.len() as u32) as usize);
+            all
+        }
+    };
+
+    let mut board = vec![];
+    for card in &in_play {
+        board.push(card.clone());
+        board.push(card.clone());
+    }
+    board.shuffle(rng);
+    board
+}
+
+impl Clone for Deck {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            resource_location: self.resource_location.clone(),
+            cards: self.cards.clone(),
+            tags: self.tags.clone(),
+            pairs: self.pairs,
+            card_back: self.card_back.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    // a registry of cards named a, b, c and so on, every other one tagged "even"
+    fn cards(count : usize) -> Registry<Card> {
+        let mut cards = Registry::new();
+        for i in 0..count {
+            let name = ((b'a' + i as u8) as char).to_string();
+            let rl = ResourceLocation::new("test", &format!("cards/{}.json", name));
+            let tags = if i % 2 == 0 {vec![String::from("even")]} else {vec![]};
+            cards.insert(rl.clone(), Card::create(name, rl.clone(), rl, tags));
+        }
+        cards
+    }
+
+    fn deck(pairs : Option<u32>) -> Deck {
+        Deck::create(String::from("Even"), ResourceLocation::new("test", "decks/even.json"), vec![], vec![String::from("even")], pairs, ResourceLocation::empty())
+    }
+
+    fn names(board : &[Card]) -> Vec<String> {
+        board.iter().map(|card| card.get_name()).collect()
+    }
+
+    #[test]
+    fn same_seed_deals_the_same_board() {
+        let cards = cards(12);
+        let deck = deck(Some(4));
+        for seed in [0, 1, 20240131] {
+            let first = deal(Some(&deck), &cards, None, &mut StdRng::seed_from_u64(seed));
+            let second = deal(Some(&deck), &cards, None, &mut StdRng::seed_from_u64(seed));
+            assert_eq!(names(&first), names(&second));
+            let first = deal(None, &cards, Some(5), &mut StdRng::seed_from_u64(seed));
+            let second = deal(None, &cards, Some(5), &mut StdRng::seed_from_u64(seed));
+            assert_eq!(names(&first), names(&second));
+        }
+    }
+
+    #[test]
+    fn different_seeds_deal_different_boards() {
+        let cards = cards(12);
+        let first = deal(None, &cards, None, &mut StdRng::seed_from_u64(1));
+        let second = deal(None, &cards, None, &mut StdRng::seed_from_u64(2));
+        assert_ne!(names(&first), names(&second));
+    }
+
+    #[test]
+    fn every_card_is_dealt_twice() {
+        let cards = cards(12);
+        let mut board = names(&deal(Some(&deck(Some(4))), &cards, None, &mut StdRng::seed_from_u64(7)));
+        assert_eq!(board.len(), 8);
+        board.sort();
+        for pair in board.chunks(2) {
+            assert_eq!(pair[0], pair[1]);
+        }
+        // only cards with the deck's tag are picked
+        assert!(board.iter().all(|name| ["a", "c", "e", "g", "i", "k"].contains(&name.as_str())));
+    }
+
+    #[test]
+    fn pairs_are_capped_by_the_pool() {
+        let cards = cards(6);
+        assert_eq!(deck(None).get_pairs(&cards), 3);
+        assert_eq!(deck(Some(10)).get_pairs(&cards), 3);
+        assert_eq!(deck(Some(2)).get_pairs(&cards), 2);
+    }
+}
@@ -5,17 +5,39 @@ use sdl2::mouse::MouseButton;
 use sdl2::rect::Rect;
 use sdl2::render::Texture;
 use uuid::Uuid;
+use crate::animation::AnimatedAssetData;
 use crate::entity::Entity;
-use crate::game::{Game, Turn};
+use crate::game::Game;
 use crate::render::AssetData;
 use crate::resource_location::ResourceLocation;
+use crate::rules::{CardState, Outcome};
+use crate::tween::{Easing, Tween};
 use crate::utils::create_uuid;
 
+// how long the animations take, in seconds
+const FLIP_TIME : f32 = 0.2;
+const PULSE_TIME : f32 = 0.35;
+const SHAKE_TIME : f32 = 0.3;
+const LIFT_TIME : f32 = 0.1;
+
+/// How long a card takes to fly from the pile to its place when it is dealt
+pub const DEAL_FLIGHT : f32 = 0.35;
+
+// the delay between each card's back starting to animate
+const BACK_RIPPLE : f32 = 0.05;
+
+// how far a hovered card is lifted up
+const LIFT_HEIGHT : f32 = 8.0;
+
+// how far the card grows at the height of a pulse, and how far it moves either way when shaken
+const PULSE_SIZE : f32 = 0.15;
+const SHAKE_DISTANCE : f32 = 3.0;
+
 /// The entity type for cards
 pub struct CardEntity {
     coords: (f32, f32),
     pub asset_data: AssetData,
-    back_texture : AssetData,
+    back : AnimatedAssetData,
     // hitbox : matrix,
     velocity : (f32, f32),
     uuid : Uuid,
@@ -29,9 +51,16 @@ pub struct CardEntity {
     x: f32,
     y : f32,
     card: Card,
-    turn: Turn,
+    board_index : usize,
+    hitbox : (u32, u32),
     success : bool,
-    prev_selected : u8
+    last_state : Option<CardState>, // the state last frame, None before the first
+    face_shown : bool, // which side is drawn, which lags behind selected during a flip
+    flip : f32, // time left of each animation, 0 when it isn't playing
+    pulse : f32,
+    shake : f32,
+    lift : Tween<f32>, // how far the card is raised off the board
+    deal : Option<Tween<(f32, f32)>> // the flight from the pile at the start of the match
 }
 
 
@@ -61,6 +90,17 @@ impl Entity for CardEntity {
 
         // get the game instance
         let game = unsafe { &mut *self.game.unwrap() };
+        self.back.tick(delta);
+
+        // fly out from the pile while the cards are dealt, the mouse is ignored until every card is down
+        if game.is_dealing() {
+            self.coords = match &mut self.deal {
+                Some(deal) => {deal.tick(delta)}
+                None => {(self.x, self.y)}
+            };
+            return;
+        }
+        self.deal = None;
         // get the player, which in this game is a dummy entity representing the camera
         let mut player : &mut dyn Entity;
         if game.get_player().is_some() {
@@ -73,29 +113,43 @@ impl Entity for CardEntity {
         // get the screen coordinates of the entity
         let screen = self.screen(player.get_coords());
 
-        // if previous selected count wasn't 0 and game selected count is 0 now,
-        if self.prev_selected!=0  && game.selected_count ==0 {
-            // if this card was selected last frame, and the previous turn was a success
-            if game.prev_success && self.selected {
-                self.success = true; // set success to true
-            }
-            self.hover = false; // reset hover
-            self.selected = false; // reset selected
-            //self.turn = self.turn.swith();
+        // the rules decide whether this card is face up or has been matched
+        let state = game.rules.get_state(self.board_index);
+        self.selected = state == Some(CardState::FaceUp);
+        self.success = state == Some(CardState::Matched);
+
+        // pulse when the pair is found, and shake a pair that didn't match before turning it back over.
+        // A continued match starts with its cards already the right way up
+        match (self.last_state, state) {
+            (None, _) => {self.face_shown = self.selected || self.success}
+            (Some(CardState::FaceUp), Some(CardState::Matched)) => {self.pulse = PULSE_TIME}
+            (Some(CardState::FaceUp), Some(CardState::FaceDown)) => {self.shake = SHAKE_TIME}
+            _ => {}
         }
-        self.prev_selected = game.selected_count; // previous selected is set for next frame
+        self.last_state = state;
+        self.animate(delta);
+
         if self.success {
-            self.coords = (self.x, self.y); // if successfully picked, be locked into the default card position
+            self.hover = false;
+            let lift = self.lift(delta);
+            self.coords = (self.x, self.y - lift); // if successfully picked, settle back into the default card position
             return;
         }
 
-        // check if mouse is hovering over
-        if (screen.0-23..screen.0+22).contains(&(game.mouse.0 as i32)) && (screen.1-34..screen.1+(if self.hover {42} else {34})).contains(&(game.mouse.1 as i32)) {
+        // check if mouse is hovering over, the hitbox starts at the top left of the card as any overlapping cards cover its bottom right
+        let origin = self.back.get_still().origin;
+        let left = screen.0 - origin.0;
+        let top = screen.1 - origin.1;
+        if (left..left + self.hitbox.0 as i32).contains(&(game.mouse.0 as i32)) && (top..top + self.hitbox.1 as i32 + (if self.hover {8} else {0})).contains(&(game.mouse.1 as i32)) {
             self.hover = true; // if it is, set hover state to true
         }
         else {
             self.hover = false; // if not set it to false, in case it was last frame.
         }
+        // the computer picks its own cards, so ignore the mouse on its turn
+        if !game.is_human_turn() {
+            self.hover = false;
+        }
         // if hovering and not selected
         if self.hover && !self.selected {
             for events in game.events.clone() {
@@ -104,18 +158,10 @@ impl Entity for CardEntity {
                         mouse_btn: MouseButton::Left,
                         ..
                     } => {
-                        self.selected = true; // set selected to true
-                        game.play_sound(ResourceLocation::new("memory_game", "sounds/flip.ogg"));
-                        if game.selected_count==0 { // if none selected
-                            game.selected_count+=1; // increment selected counter
-                            game.selected_cards.0 = self.card.clone() // set the first value to this card
-                        }
-                        else if game.selected_count==1 { // if one already selected
-                            game.selected_count+=1; // increment selected counter
-                            game.selected_cards.1 = self.card.clone() // set second value to this card
-                        }
-                        else {
-                            // nothing
+                        // hand the selection over to the game, which checks it against the rules
+                        match game.select_card(self.board_index) {
+                            Outcome::Rejected(_) => {}
+                            _ => {self.selected = true} // set selected to true
                         }
                     },
                     _ => {}
@@ -125,14 +171,15 @@ impl Entity for CardEntity {
         if self.selected { // if selected, hover
             self.hover = true;
         }
-        if self.hover { // if hovering, offset the card upwards and set the mouse to the finger
-            self.coords = (self.x, self.y - 8.0);
-            if !self.selected {
-                game.use_finger = true;
-            }
+        if self.hover && !self.selected { // if hovering, set the mouse to the finger
+            game.use_finger = true;
         }
-        else { // otherwise set to default coordinates, incase it was hovered last frame.
-            self.coords = (self.x, self.y)
+        // lift the card up while hovered, gliding back down to the default coordinates when it isn't
+        let lift = self.lift(delta);
+        self.coords = (self.x, self.y - lift);
+        if self.shake > 0.0 {
+            let progress = 1.0 - self.shake / SHAKE_TIME;
+            self.coords.0 += (progress * std::f32::consts::PI * 6.0).sin() * SHAKE_DISTANCE * (1.0 - progress);
         }
         if self.hover && !self.prev_hover {
             game.play_sound(ResourceLocation::new("memory_game", "sounds/pop.ogg"))
@@ -158,38 +205,54 @@ impl Entity for CardEntity {
 
     fn get_asset_data(&mut self) -> AssetData {
 
-        // if the card is selected or successfully picked, display front texture
-        if self.selected || self.success {
+        // if the face is showing, display front texture
+        if self.face_shown {
             self.asset_data.clone()
         }
         // otherwise use the default back texture
         else {
-            self.back_texture.clone()
+            match self.game {
+                Some(game) => {self.back.current(&unsafe { &*game }.assets.animations)}
+                None => {self.back.get_still().clone()}
+            }
         }
     }
 
     fn set_asset_data(&mut self, ass: AssetData) {
         self.asset_data = ass;
     }
+
+    fn get_scale(&mut self) -> (f32, f32) {
+        // squash to nothing and back out again while flipping
+        let across = if self.flip > 0.0 {(self.flip / FLIP_TIME * 2.0 - 1.0).abs()} else {1.0};
+        // grow and shrink back while pulsing
+        let size = if self.pulse > 0.0 {1.0 + (self.pulse / PULSE_TIME * std::f32::consts::PI).sin() * PULSE_SIZE} else {1.0};
+        (across * size, size)
+    }
 }
 
 impl CardEntity {
 
     /// Create a card entity instance
-    pub fn create(game : Option<*mut Game>, ass : AssetData, x : f32, y : f32, card: Card) -> CardEntity {
+    pub fn create(game : Option<*mut Game>, ass : AssetData, x : f32, y : f32, card: Card, board_index : usize) -> CardEntity {
 
         let uuid = create_uuid();
 
         Self {
             coords: (x, y),
             asset_data: ass,
-            back_texture: AssetData {
-                uv: Some(Rect::new(0,0,45,68)),
-                origin: (23, 34),
-                resource_location: ResourceLocation {
-                    namespace: "memory_game".to_string(),
-                    path: "cards/card_reverse.png".to_string(),
-                },
+            back: {
+                let mut back = AnimatedAssetData::create(None, AssetData {
+                    uv: Some(Rect::new(0,0,45,68)),
+                    origin: (23, 34),
+                    resource_location: ResourceLocation {
+                        namespace: "memory_game".to_string(),
+                        path: "cards/card_reverse.png".to_string(),
+                    },
+                });
+                // each card starts a little after the one before, so any animation ripples across the board
+                back.set_elapsed(-(board_index as f32) * BACK_RIPPLE);
+                back
             },
             velocity: (0.0, 0.0),
             uuid,
@@ -203,11 +266,69 @@ impl CardEntity {
             x,
             y,
             card,
-            turn: Turn::Player1,
+            board_index,
+            hitbox: (45, 68),
             success: false,
-            prev_selected : 0
+            last_state: None,
+            face_shown: false,
+            flip: 0.0,
+            pulse: 0.0,
+            shake: 0.0,
+            lift: Tween::still(0.0),
+            deal: None
         }
     }
+
+    /// Start the card at `pile`, flying to its place after `delay` seconds. `on_land` is run as it gets there
+    pub fn deal_from(&mut self, pile : (f32, f32), delay : f32, on_land : impl FnOnce() + 'static) {
+        self.coords = pile;
+        self.deal = Some(Tween::create(pile, (self.x, self.y), DEAL_FLIGHT, Easing::EaseInOut).delay(delay).on_complete(on_land));
+    }
+
+    // move the lift towards where it should be for the hover state, giving how high the card is
+    fn lift(&mut self, delta : f32) -> f32 {
+        let target = if self.hover {LIFT_HEIGHT} else {0.0};
+        if self.lift.target() != target {
+            self.lift = Tween::create(self.lift.value(), target, LIFT_TIME, Easing::EaseOut);
+        }
+        self.lift.tick(delta)
+    }
+
+    // count down the animations, starting a flip when the side shown is out of date. A shaking card finishes shaking before it turns over
+    fn animate(&mut self, delta : f32) {
+        self.pulse = (self.pulse - delta).max(0.0);
+        self.shake = (self.shake - delta).max(0.0);
+
+        let face_up = self.selected || self.success;
+        if self.flip > 0.0 {
+            self.flip = (self.flip - delta).max(0.0);
+            // swap sides halfway, when the card is edge on
+            if self.flip < FLIP_TIME / 2.0 {
+                self.face_shown = face_up;
+            }
+        }
+        else if self.face_shown != face_up && self.shake == 0.0 {
+            self.flip = FLIP_TIME;
+        }
+    }
+
+    /// Set the texture shown on the back of the card. An animation data file can be used instead, the default back is shown if it isn't loaded
+    pub fn set_back_texture(&mut self, rl : ResourceLocation) {
+        if rl.path.ends_with(".json") {
+            self.back.set_animation(Some(rl));
+        }
+        else {
+            let mut still = self.back.get_still().clone();
+            still.resource_location = rl;
+            self.back.set_still(still);
+            self.back.set_animation(None);
+        }
+    }
+
+    /// Set the clickable area of the card, measured from its top left corner
+    pub fn set_hitbox(&mut self, hitbox : (u32, u32)) {
+        self.hitbox = hitbox
+    }
     pub fn set_game(&mut self, game : *mut Game) {
         self.game = Some(game)
     }
@@ -217,16 +338,18 @@ impl CardEntity {
 pub struct Card {
     name : String, // The name of the card
     resource_location: ResourceLocation, // The resource location of the card data file
-    texture: ResourceLocation // The resource location of the card texture
+    texture: ResourceLocation, // The resource location of the card texture
+    tags: Vec<String> // Tags decks can use to pick the card up
 }
 impl Card {
 
     ///Create a new card instance
-    pub fn create(name: String, resource_location: ResourceLocation, texture : ResourceLocation) -> Self {
+    pub fn create(name: String, resource_location: ResourceLocation, texture : ResourceLocation, tags : Vec<String>) -> Self {
         Self{
             name,
             resource_location,
-            texture
+            texture,
+            tags
         }
     }
 
@@ -244,12 +367,18 @@ impl Card {
         self.texture.clone()
     }
 
+    /// Get the card tags
+    pub fn get_tags(&self) -> Vec<String> {
+        self.tags.clone()
+    }
+
     /// Create an empty card instance
     pub fn empty() -> Self{
         Self {
             name: "Blank Card".to_string(),
             resource_location: ResourceLocation::empty(),
             texture: ResourceLocation::new("memory_game", "memory_game:cards/card_base.png"),
+            tags: vec![],
         }
     }
  }
@@ -260,7 +389,8 @@ impl Clone for Card {
         Self{
             name : self.name.clone(),
             resource_location : self.resource_location.clone(),
-            texture : self.texture.clone()
+            texture : self.texture.clone(),
+            tags : self.tags.clone()
         }
     }
 }
\ No newline at end of file
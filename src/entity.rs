@@ -108,6 +108,16 @@ pub trait Entity {
 
     }
 
+    /// How much the texture is stretched when drawn, across and down. Animations change this, like a card squashing as it flips
+    fn get_scale(&mut self) -> (f32, f32) {
+        (1.0, 1.0)
+    }
+
+    /// How solid the texture is drawn, from 0 for invisible to 255
+    fn get_alpha(&mut self) -> u8 {
+        255
+    }
+
 
 }
 
@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufReader;
 use std::ops::DerefMut;
@@ -10,28 +9,55 @@ use chrono::Month;
 use rodio::{Decoder, OutputStream, source::Source, OutputStreamHandle};
 use log::{info, warn};
 use num::bigint::U32Digits;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use sdl2::event::Event;
 use sdl2::keyboard::{Keycode, Scancode};
 use sdl2::keyboard::Keycode::C;
 use sdl2::mouse::MouseButton;
 use sdl2::rect::Rect;
 use sdl2::render::{Texture, WindowCanvas};
+use crate::assets::{Assets, Registry};
 use crate::entity::{Entity};
 use crate::level::{Level, TileGraph};
-use crate::{entities, render, sound};
-use crate::entities::card_entity::{Card, CardEntity};
+use crate::loader::LoadError;
+use crate::{deck, entities, render, sound};
+use crate::entities::card_entity::{CardEntity, DEAL_FLIGHT};
 use crate::entities::dummy::DummyEntity;
-use crate::game::Turn::Player2;
-use crate::render::{draw_pp_texture, AssetData};
+use crate::board::BoardLayout;
+use crate::ai::Difficulty;
+use crate::player::Player;
+use crate::replay::Replay;
+use crate::save::SavedMatch;
+use crate::settings::Settings;
+use crate::stats::Stats;
+use crate::rules::{CardState, MatchRules, Outcome};
+use crate::render::{draw_pp_texture, AssetData, Transform, TARGET_DIMENSIONS};
 use crate::resource_location::ResourceLocation;
-use crate::screen::Screen;
-use crate::sound::{AudioManager, Sound};
-use crate::tile::{Tile, TileSize};
+use crate::screen::{Screen, ScreenCommand};
+use crate::screens::hud_screen::HudScreen;
+use crate::screens::load_error_screen::LoadErrorScreen;
+use crate::screens::main_menu_screen::MainMenuScreen;
+use crate::screens::pause_screen::PauseScreen;
+use crate::settings::TransitionStyle;
+use crate::sound::AudioManager;
+use crate::transition::Transition;
 use crate::utils::order_sort;
 use crate::widget::{Alignment, Widget};
 use crate::widgets::play_widget::PlayWidget;
 
+// where cards are dealt from at the start of a match, just off the bottom of the screen, and the time between each card leaving it
+const DECK_PILE : (f32, f32) = (0.0, TARGET_DIMENSIONS.1 as f32 / 2.0 + 40.0);
+const DEAL_INTERVAL : f32 = 0.08;
+
+// the deck, pairs, players and opponent a match is played with
+struct MatchChoices {
+    deck : Option<String>,
+    pairs : Option<u32>,
+    players : usize,
+    computer : Option<Difficulty>
+}
+
 /// An object that manages a game instance. It holds all game data and manages the render, physics and screen loops
 pub struct Game {
     pub entities: Vec<Box<Mutex<dyn Entity>>>, // new (uses traits) (better)
@@ -40,9 +66,10 @@ pub struct Game {
     pub held_keys : Vec<Scancode>,
     pub running : bool,
     pub current_level : Option<Level>,
-    pub current_screen : Option<Box<dyn Screen>>,
-    pub tiles :  HashMap<String, Tile>,
-    pub sounds : HashMap<String, Sound>,
+    pub screens : Vec<Box<dyn Screen>>, // the screen stack, the last one is on top and gets the input
+    screen_commands : Vec<ScreenCommand>,
+    pub transition : Option<Transition>, // plays over the game when the screen changes
+    pub assets : Assets, // tiles, cards, decks and sounds
     pub draw_mouse : bool,
     pub sf : i32,
     pub use_finger : bool,
@@ -50,119 +77,147 @@ pub struct Game {
     pub score : f32,
     debug : bool,
     pub mouse : (u32, u32),
-    pub cards : HashMap<String, Card>,
-    pub selected_count : u8,
-    pub selected_cards : (Card, Card),
-    pub current_turn : Turn,
+    pub deck : Option<String>,
+    pub pair_count : Option<u32>,
+    pub player_count : usize,
+    pub players : Vec<Player>,
+    pub computer : Option<Difficulty>,
+    menu_choices : Option<MatchChoices>, // the choices made in the menus, set aside while a saved match or replay uses its own
+    pub seed : Option<u64>, // the seed asked for, if not set every match gets a random one
+    pub match_seed : u64, // the seed the current match was dealt with
+    rng : StdRng, // all randomness in a match comes from here, so a seed always plays out the same way
+    pub recording : Replay, // the flips made so far this match
+    pub playback : Option<Replay>, // the replay being watched, if any
+    playback_position : usize,
+    pub stats : Stats,
+    pub settings : Settings,
+    pub resume : Option<SavedMatch>, // the saved match to pick back up when the next scene is created
+    pub load_errors : Vec<LoadError>, // files that were skipped when loading, shown on the error screen
+    pub reload_assets : bool, // set to load everything again at the start of the next frame, like when the resource packs change
+    streak : u32, // pairs found in a row this turn
+    best_streak : u32, // the longest run of pairs this match
+    pub rules : MatchRules,
     pub wait_timer : f32,
-    pub prev_success : bool,
-    player_1_score : u32,
-    player_2_score : u32,
+    pub deal_timer : f32, // time left until every card has been dealt at the start of a match
+    pub paused : bool,
+    pub typing : bool, // a widget is taking key presses, so Escape stops the typing instead of going back
     audio_manager: AudioManager
 }
 
-#[derive(Debug)]
-pub enum Turn {
-    Player1,
-    Player2
-}
-
-impl Turn {
-    pub(crate) fn clone(&self) -> Turn {
-        match self {
-            Turn::Player1 => {Self::Player1}
-            Turn::Player2 => {Self::Player2}
-        }
-    }
-}
-
-impl Turn {
-    pub fn swith(&self) -> Self{
-        match self {
-            Turn::Player1 => {Turn::Player2}
-            Turn::Player2 => {Turn::Player1}
-        }
-    }
-}
-
-impl PartialEq for Turn {
-    fn eq(&self, other: &Self) -> bool {
-        let mut bl1 = false;
-        match other {
-            Turn::Player1 => {bl1 = true;}
-            Turn::Player2 => {}
-        }
-        let mut bl2 = false;
-        match self {
-            Turn::Player1 => {bl2 = true;}
-            Turn::Player2 => {}
-        }
-        (!bl1 && !bl2) || (bl1 && bl2)
-    }
-}
-
 impl Game {
 
     /// Physics and inputs
     pub fn cycle(&mut self, delta : f32, mousex : u32, mousey : u32, dims : (u32, u32)) {
 
-        if self.selected_count ==2 {
-            info!("{:?} picked {} and {}", self.current_turn, self.selected_cards.0.get_name(), self.selected_cards.1.get_name());
-            self.current_turn = self.current_turn.swith();
-            self.selected_count = 0;
-            self.prev_success = self.selected_cards.0.get_name() == self.selected_cards.1.get_name();
-            self.selected_cards = (Card::empty(), Card::empty());
-            self.wait_timer = 2.0;
-            if self.prev_success {
-                self.current_turn = self.current_turn.swith();
+        // play the screen transition, changing the screen once the old one is covered
+        if let Some(transition) = &mut self.transition {
+            transition.tick(delta);
+            let pending = if transition.is_covered() {transition.take_pending()} else {vec![]};
+            if transition.is_done() {
+                self.transition = None;
             }
-            info!("{:?}'s turn", self.current_turn)
+            self.run_screen_commands(pending);
         }
+        let frozen = self.paused || self.transition.is_some();
 
-        if !self.entities.is_empty() {
+        // count up the match time until every pair is found
+        if !frozen && !self.entities.is_empty() && !self.rules.is_over() && !self.is_dealing() {
             self.score += delta;
         }
 
-        if self.wait_timer < 0.0 {
-            // Run physics for every entity
-            for entity in self.entities.iter() {
-                entity.lock().unwrap().physics(delta)
+        // nothing in the match moves while it is paused, or while the screen is changing
+        if frozen {}
+        // nobody can flip a card until they have all been dealt
+        else if self.is_dealing() {
+            self.deal_timer -= delta;
+        }
+        else if self.wait_timer < 0.0 {
+            // once the picked pair has been shown for long enough, settle it
+            if self.rules.pair_pending() {
+                self.resolve_pair();
             }
-            if self.prev_success {
-                match self.current_turn {
-                    Turn::Player1 => {self.player_1_score+=1;}
-                    Turn::Player2 => {self.player_2_score+=1;}
-                }
-                if self.player_1_score + self.player_2_score == 9 {
-                    if self.player_1_score > self.player_2_score {
-                        info!("Player 1 wins!")
+            // when watching a replay, make each flip once the match clock reaches it
+            else if self.playback.is_some() {
+                let next = self.playback.as_ref().and_then(|replay| replay.get_flip(self.playback_position));
+                if let Some((time, index)) = next {
+                    if self.score >= time {
+                        self.playback_position += 1;
+                        self.select_card(index);
                     }
-                    else {
-                        info!("Player 2 wins!")
-                    }
-
                 }
             }
-            self.prev_success = false;
+            // let the computer take its turn
+            else if !self.rules.is_over() {
+                let turn = self.rules.current_turn();
+                let choice = match self.players.get_mut(turn).and_then(|p| p.get_ai_mut()) {
+                    Some(ai) => {ai.think(delta, &self.rules, &mut self.rng)}
+                    None => {None}
+                };
+                if let Some(index) = choice {
+                    self.select_card(index);
+                }
+            }
         }
         else {
             self.wait_timer -= delta;
         }
 
-        // if there is a current screen, run its cycle function
-        let _ = if self.current_screen.is_some() {
-            self.current_screen.as_mut().unwrap().cycle(mousex, mousey, self.events.clone())
-        };
+        // Run physics for every entity, including while a pair is shown so the cards finish turning over
+        if !frozen {
+            for entity in self.entities.iter() {
+                entity.lock().unwrap().physics(delta)
+            }
+        }
+
+        // animate every screen's widgets, even the ones underneath an overlay
+        for screen in self.screens.iter_mut() {
+            screen.tick(delta)
+        }
+
+        // whether something was being typed before the widgets saw this frame's keys
+        let typing = self.typing;
+
+        // if there is a current screen, run its cycle function. Only the top screen takes input, and none does during a transition
+        if let Some(screen) = self.screens.last_mut().filter(|_| self.transition.is_none()) {
+            screen.cycle(mousex, mousey, self.events.clone())
+        }
 
         // handle user inputs
         for event in self.events.clone() {
             match event {
+                Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } if typing => {
+                    // the widget being typed in has already used it to stop typing
+                },
+                Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } if !self.entities.is_empty() && self.screens.last().is_some_and(|screen| screen.toggles_pause()) => {
+                    // during a match, Escape pauses instead of quitting
+                    if self.paused {
+                        self.resume();
+                    }
+                    else {
+                        self.pause();
+                    }
+                },
+                Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } if self.screens.len() > 1 => {
+                    // in other menus, including the ones opened from the pause menu, Escape goes back a screen
+                    self.pop_screen();
+                },
                 Event::Quit { .. }
                 | Event::KeyDown {
                     keycode: Some(Keycode::Escape),
                     ..
                 } => {
                     info!("Quitting game!");
+                    // keep the match to continue next time
+                    self.save_match();
                     // close game on Escape, or app closure
                     self.running=false
                 },
@@ -172,13 +227,20 @@ impl Game {
                 } => {
                     self.debug= !self.debug
                 },
+                Event::MouseButtonDown {
+                    mouse_btn : MouseButton::Left,
+                    ..
+                } if self.is_dealing() && !frozen => {
+                    // clicking during the deal lays every card down straight away
+                    self.deal_timer = 0.0;
+                },
                 Event::MouseButtonDown {
                     mouse_btn : MouseButton::Left,
                     ..
                 } => {
-                    // on left click, check if the mouse is over a widget, if so, execute its on_click function
-                    if self.current_screen.is_some() {
-                        for wl in self.current_screen.as_mut().unwrap().get_widgets() {
+                    // on left click, check if the mouse is over a widget, if so, execute its on_click function. Nothing can be clicked during a transition
+                    if let Some(screen) = self.screens.last_mut().filter(|_| self.transition.is_none()) {
+                        for wl in screen.get_widgets() {
                             for w in wl {
                                 if w.get_selected() {
                                     w.on_click()
@@ -193,6 +255,86 @@ impl Game {
                 }
             }
         }
+
+        if let Some(screen) = self.screens.last_mut() {
+            screen.update();
+        }
+
+        // now nothing is using the screens, change them over
+        self.apply_screen_commands();
+    }
+
+    /// Open a screen on top of the current one, it is shown once the frame is over
+    pub fn push_screen(&mut self, screen : Box<dyn Screen>) {
+        self.screen_commands.push(ScreenCommand::Push(screen))
+    }
+
+    /// Close the top screen, going back to the one below it
+    pub fn pop_screen(&mut self) {
+        self.screen_commands.push(ScreenCommand::Pop)
+    }
+
+    /// Swap the top screen for another
+    pub fn replace_screen(&mut self, screen : Box<dyn Screen>) {
+        self.screen_commands.push(ScreenCommand::Replace(screen))
+    }
+
+    /// Close every screen and open the given one
+    pub fn reset_screens(&mut self, screen : Box<dyn Screen>) {
+        self.screen_commands.push(ScreenCommand::Reset(screen))
+    }
+
+    // apply the screen changes asked for this frame. When the whole screen changes they wait for a transition to cover it
+    fn apply_screen_commands(&mut self) {
+        let commands = std::mem::take(&mut self.screen_commands);
+        match &mut self.transition {
+            Some(transition) if !transition.is_covered() => {
+                transition.add(commands);
+                return
+            }
+            Some(_) => {}
+            None => {
+                let style = self.settings.transition;
+                if style != TransitionStyle::Off && commands.iter().any(|command| self.changes_screen(command)) {
+                    self.transition = Some(Transition::create(style, self.settings.transition_time, commands));
+                    return
+                }
+            }
+        }
+        self.run_screen_commands(commands);
+    }
+
+    // whether a screen change replaces everything shown, rather than opening or closing an overlay like the pause menu.
+    // Replacing the top screen is left out, as it is used to rebuild a screen in place
+    fn changes_screen(&self, command : &ScreenCommand) -> bool {
+        match command {
+            ScreenCommand::Push(screen) | ScreenCommand::Reset(screen) => {!screen.is_overlay()}
+            ScreenCommand::Pop => {self.screens.len() > 1 && self.screens.last().is_some_and(|screen| !screen.is_overlay())}
+            ScreenCommand::Replace(_) => {false}
+        }
+    }
+
+    // make screen changes, in order
+    fn run_screen_commands(&mut self, commands : Vec<ScreenCommand>) {
+        for command in commands {
+            match command {
+                ScreenCommand::Push(screen) => {self.screens.push(screen)}
+                ScreenCommand::Pop => {
+                    // never leave the game without a screen
+                    if self.screens.len() > 1 {
+                        self.screens.pop();
+                    }
+                }
+                ScreenCommand::Replace(screen) => {
+                    self.screens.pop();
+                    self.screens.push(screen)
+                }
+                ScreenCommand::Reset(screen) => {
+                    self.screens.clear();
+                    self.screens.push(screen)
+                }
+            }
+        }
     }
 
     /// Returns the entity assigned as the "player", may not always be [`Player`]
@@ -202,29 +344,170 @@ impl Game {
         self.entities.get_mut(self.player.unwrap())
     }
 
+    // settle the face up pair, and keep track of streaks and finished matches
+    fn resolve_pair(&mut self) {
+        let outcome = self.rules.resolve();
+        for player in self.players.iter_mut() {
+            if let Some(ai) = player.get_ai_mut() {
+                ai.end_turn(&self.rules, &mut self.rng);
+            }
+        }
+        match outcome {
+            Outcome::Matched { game_over, .. } => {
+                self.streak += 1;
+                self.best_streak = self.best_streak.max(self.streak);
+                // keep a record of every finished match, but not of watching one back
+                if game_over && self.playback.is_none() {
+                    self.recording.save();
+                    self.record_stats();
+                    SavedMatch::delete();
+                }
+            }
+            Outcome::Mismatched { .. } => {self.streak = 0}
+            _ => {}
+        }
+    }
+
+    /// Save the match in progress so it can be continued later, called when the game is closed
+    pub fn save_match(&mut self) {
+        if self.entities.is_empty() || self.rules.is_over() || self.playback.is_some() {
+            return
+        }
+        // a pair that has been picked is settled first, a single card is turned back over as if it was never picked
+        if self.rules.pair_pending() {
+            self.resolve_pair();
+            if self.rules.is_over() {
+                return
+            }
+        }
+        else if (0..self.rules.card_count()).any(|i| self.rules.get_state(i) == Some(CardState::FaceUp)) {
+            self.recording.undo();
+        }
+        let names = self.players.iter().map(|p| p.get_name()).collect();
+        let recording = std::mem::replace(&mut self.recording, Replay::create(0, None, None, 1));
+        SavedMatch::create(&self.rules, self.deck.clone(), self.pair_count, self.computer, names, self.score, recording).save();
+    }
+
+    /// Freeze the match and show the pause menu over it
+    pub fn pause(&mut self) {
+        info!("Pausing the match");
+        self.paused = true;
+        let screen = PauseScreen::create(self);
+        self.push_screen(screen);
+    }
+
+    /// Carry on with the paused match
+    pub fn resume(&mut self) {
+        if self.paused {
+            self.paused = false;
+            self.pop_screen();
+        }
+    }
+
+    /// Deal the match again from the start, with the same settings
+    pub fn restart(&mut self) {
+        if self.paused {
+            self.pop_screen();
+        }
+        self.create_memory_game_scene();
+        let screen = HudScreen::create(self);
+        self.replace_screen(screen);
+    }
+
+    /// Leave the match for the main menu, saving it so it can be continued
+    pub fn return_to_menu(&mut self) {
+        self.save_match();
+        self.entities = vec![];
+        self.current_level = None;
+        self.paused = false;
+        self.deal_timer = 0.0;
+        self.playback = None;
+        if let Some(choices) = self.menu_choices.take() {
+            self.swap_match_choices(choices);
+        }
+        let screen = MainMenuScreen::create(self);
+        self.reset_screens(screen);
+    }
+
+    /// Pick the saved match back up, if there is one
+    pub fn continue_match(&mut self) {
+        let saved = match SavedMatch::load() {
+            Some(saved) => {saved}
+            None => {
+                warn!("There is no saved match to continue");
+                return
+            }
+        };
+        info!("Continuing a {} player match on seed {}", saved.players(), saved.seed());
+        self.use_match_choices(MatchChoices { deck: saved.deck.clone(), pairs: saved.pairs, players: saved.players(), computer: saved.computer });
+        self.playback = None;
+        self.resume = Some(saved);
+        self.create_memory_game_scene();
+        let screen = HudScreen::create(self);
+        self.push_screen(screen);
+    }
+
+    /// Flip the card at a given board index, this is the path every card selection goes through
+    pub fn select_card(&mut self, index : usize) -> Outcome {
+        let outcome = self.rules.flip(index);
+        match outcome {
+            Outcome::Rejected(_) => {return outcome}
+            Outcome::PairRevealed { .. } => {
+                self.play_sound(ResourceLocation::new("memory_game", "sounds/flip.ogg"));
+                // show the pair for a bit before it is resolved
+                self.wait_timer = self.settings.reveal_delay;
+            }
+            _ => {
+                self.play_sound(ResourceLocation::new("memory_game", "sounds/flip.ogg"));
+            }
+        }
+        self.recording.record(self.score, index);
+
+        // everyone sees the card that was turned over, including the computer
+        for player in self.players.iter_mut() {
+            if let Some(ai) = player.get_ai_mut() {
+                ai.observe(index, &self.rules);
+            }
+        }
+        outcome
+    }
+
+    /// Whether the cards are still flying out to the board at the start of a match
+    pub fn is_dealing(&self) -> bool {
+        self.deal_timer > 0.0
+    }
+
+    /// Whether a person is picking the cards this turn, if not mouse clicks on the board are ignored
+    pub fn is_human_turn(&self) -> bool {
+        if self.playback.is_some() {
+            return false
+        }
+        self.players.get(self.rules.current_turn()).is_none_or(|p| p.is_human())
+    }
+
     pub fn create_memory_game_scene(&mut self) {
-        self.current_turn = Turn::Player1;
-        self.selected_count = 0;
-        self.selected_cards = (Card::empty(), Card::empty());
         self.entities = vec![];
-        self.prev_success = false;
         self.wait_timer = -1.0;
-        self.player_1_score = 0;
-        self.player_2_score = 0;
-        let mut positions: Vec<(f32, f32)> = vec![
-            // Column 1 (Far Left)
-            (-104.0, -70.0), (-104.0, 0.0), (-104.0, 70.0),
-            // Column 2 (Left)
-            (-56.0, -70.0), (-56.0, 0.0), (-56.0, 70.0),
-            // Column 3 (Left Center)
-            (-8.0, -70.0), (-8.0, 0.0), (-8.0, 70.0),
-            // Column 4 (Right Center)
-            (40.0, -70.0), (40.0, 0.0), (40.0, 70.0),
-            // Column 5 (Right)
-            (88.0, -70.0), (88.0, 0.0), (88.0, 70.0),
-            // Column 6 (Far Right)
-            (136.0, -70.0), (136.0, 0.0), (136.0, 70.0)
-        ];
+        self.paused = false;
+        self.score = 0.0;
+
+        // a saved match picks up where it was left, otherwise any old save is replaced by the new match
+        let saved = self.resume.take().filter(|saved| saved.board(&self.assets.cards).is_some());
+        if saved.is_none() && self.playback.is_none() {
+            SavedMatch::delete();
+        }
+
+        // seed the match, so it can be dealt again by passing the same seed. A replay or saved match brings its own
+        self.match_seed = match (&saved, &self.playback) {
+            (Some(saved), _) => {saved.seed()}
+            (None, Some(replay)) => {replay.seed}
+            (None, None) => {self.seed.unwrap_or_else(rand::random)}
+        };
+        self.playback_position = 0;
+        self.streak = 0;
+        self.best_streak = 0;
+        self.rng = StdRng::seed_from_u64(self.match_seed);
+        info!("Match seed is {}", self.match_seed);
         let mut dummy_player = DummyEntity::create(self, {
             let mut ass = AssetData::empty();
             ass.resource_location = ResourceLocation::new("memory_game", "empty.png");
@@ -236,45 +519,130 @@ impl Game {
 
 
 
+        // the resource location starts as the card back, which can be a texture or an animation
         let mut card_asset_base = AssetData {
             uv: Some(Rect::new(0,0,45,68)),
             origin: (23, 34),
             resource_location: ResourceLocation {
                 namespace: "memory_game".to_string(),
-                path: "cards/card_reverse.png".to_string(),
+                path: "animations/card_back.json".to_string(),
             },
         };
+        // two of every card in play, from the chosen deck or every card if there isn't one, shuffled into the order they are laid out on the board.
+        // A saved match already has its board
+        let chosen_deck = self.deck.as_ref().and_then(|rl| self.assets.decks.find(rl));
+        if let Some(deck) = chosen_deck {
+            info!("Building match from deck {}", deck.get_resource_location().to_string());
+            card_asset_base.resource_location = deck.get_card_back();
+        }
+        let mut board = deck::deal(chosen_deck, &self.assets.cards, self.pair_count, &mut self.rng);
+        if let Some(saved_board) = saved.as_ref().and_then(|saved| saved.board(&self.assets.cards)) {
+            board = saved_board;
+        }
+
+        // lay the cards out in a grid that fits the screen
+        let layout = BoardLayout::for_cards(board.len());
+        let positions = layout.positions();
+        info!("Dealing {} cards in {} columns and {} rows", board.len(), layout.columns, layout.rows);
+
         let mut cards = vec![];
+        for (index, card) in board.iter().enumerate() {
+            let pos = positions[index];
+            let mut card = CardEntity::create(None, {
+                let mut ass = card_asset_base.clone();
+                ass.resource_location = card.get_texture_location();
+                ass
+            }, pos.0, pos.1, card.clone(), index);
+            card.set_hitbox(layout.hitbox());
+            card.set_back_texture(card_asset_base.resource_location.clone());
+            cards.push(card);
+        }
+        self.rules = match &saved {
+            Some(saved) => {saved.rules(board)}
+            None => {MatchRules::new(board, self.player_count)}
+        };
+        self.player_count = self.rules.player_count();
 
-        let mut counter = 0;
-        while counter < self.cards.len()*2 {
-            for (_,card) in &self.cards {
-
-                let index = rand::thread_rng().gen_range(0..(self.cards.len())*2-counter);
-
-                let pos = positions.get(index).unwrap().clone();
-                positions.remove(index);
-                let mut card = CardEntity::create(None, {
-                    let mut ass = card_asset_base.clone();
-                    ass.resource_location = card.get_texture_location();
-                    ass
-                }, pos.0-16.0, pos.1, card.clone());
-                cards.push(card);
-                counter +=1;
+        // players are named from the settings
+        self.players = (0..self.player_count).map(|indx| Player::create(self.settings.player_name(indx))).collect();
+        // the first player is always a person, if there is a computer opponent it plays everyone else. Replays play every flip back themselves
+        for (indx, player) in self.players.iter_mut().enumerate() {
+            player.set_computer(if indx == 0 || self.playback.is_some() {None} else {self.computer});
+        }
+        self.recording = Replay::create(self.match_seed, self.deck.clone(), self.pair_count, self.player_count);
+        if let Some(saved) = saved {
+            for (player, name) in self.players.iter_mut().zip(saved.names.iter()) {
+                player.set_name(name.clone());
             }
+            self.score = saved.time;
+            self.recording = saved.replay;
         }
+
+        // deal the cards out from the pile one at a time, with a flip sound as each one lands
+        let game = self as *mut Game;
+        for (index, card) in cards.iter_mut().enumerate() {
+            card.deal_from(DECK_PILE, index as f32 * DEAL_INTERVAL, move || {
+                unsafe { &mut *game }.play_sound(ResourceLocation::new("memory_game", "sounds/flip.ogg"))
+            });
+        }
+        // a little longer than the last card takes, so its sound still plays
+        self.deal_timer = DEAL_FLIGHT + DEAL_INTERVAL * cards.len() as f32;
+
         for mut card in cards {
             card.set_game(self);
             self.entities.push(Box::new(Mutex::new(card)))
         }
-        self.current_level = Some(Level::create_scene_background(&self.tiles));
+        self.current_level = Some(Level::create_scene_background(&self.assets.tiles));
     }
 
-    pub fn get_player_1_score(&mut self) -> u32 {
-        self.player_1_score
+    /// Watch a recorded match. The board is dealt again from the replay's seed and the flips are made as they were recorded
+    pub fn start_replay(&mut self, replay : Replay) {
+        info!("Playing back a replay with {} players on seed {}", replay.players, replay.seed);
+        self.use_match_choices(MatchChoices { deck: replay.deck.clone(), pairs: replay.pairs, players: replay.players, computer: self.computer });
+        self.playback = Some(replay);
+        self.create_memory_game_scene();
+        let screen = HudScreen::create(self);
+        self.push_screen(screen);
     }
-    pub fn get_player_2_score(&mut self) -> u32 {
-        self.player_2_score
+
+    // play a match with its own deck, pairs, players and opponent, putting the ones picked in the menus back when it is left
+    fn use_match_choices(&mut self, choices : MatchChoices) {
+        let picked = self.swap_match_choices(choices);
+        if self.menu_choices.is_none() {
+            self.menu_choices = Some(picked);
+        }
+    }
+
+    // put the choices in place, giving back the ones they replaced
+    fn swap_match_choices(&mut self, choices : MatchChoices) -> MatchChoices {
+        MatchChoices {
+            deck: std::mem::replace(&mut self.deck, choices.deck),
+            pairs: std::mem::replace(&mut self.pair_count, choices.pairs),
+            players: std::mem::replace(&mut self.player_count, choices.players),
+            computer: std::mem::replace(&mut self.computer, choices.computer)
+        }
+    }
+
+    // add the match that just finished to the saved statistics
+    fn record_stats(&mut self) {
+        if self.rules.is_solo() {
+            self.stats.record_solo(self.score, self.rules.flips(), self.rules.pair_count(), self.best_streak);
+        }
+        else {
+            let winner = self.rules.winner().and_then(|p| self.players.get(p)).map(|p| p.get_name());
+            self.stats.record_match(winner, self.best_streak);
+        }
+        self.stats.save();
+    }
+
+    /// Whether every pair on the board has been found
+    pub fn is_game_over(&self) -> bool {
+        !self.entities.is_empty() && self.rules.is_over()
+    }
+
+    /// The number of pairs a player has found, players are counted from 0
+    pub fn get_score(&mut self, player : usize) -> u32 {
+        self.rules.score(player)
     }
 
     /// The render loop for entities, screens and the mouse. The entity rendering is done here, for specifics on other elements see the render functions for [`Screens`]|[`Levels/Tiles`]|[`Widgets`]
@@ -282,7 +650,7 @@ impl Game {
     /// [`Screens`]: Screen::render
     /// [`Levels/Tiles`]: Level::render
     /// [`Widgets`]: Widget::render
-    pub fn render(&mut self, canvas: &mut WindowCanvas, sf: i32, textures : &HashMap<String, Texture>, dims : (u32, u32), mousex : u32, mousey : u32) {
+    pub fn render(&mut self, canvas: &mut WindowCanvas, sf: i32, textures : &Registry<Texture>, dims : (u32, u32), mousex : u32, mousey : u32) {
 
         // if there are entities, render them to screen
         if !self.entities.is_empty() {
@@ -307,17 +675,25 @@ impl Game {
                 let mut obj = list.get(x.1).unwrap().lock().unwrap();
                 let screen_coords = &obj.screen(player_coords);
                 let asset_data = &obj.get_asset_data();
-                draw_pp_texture(screen_coords.0, screen_coords.1, &asset_data, canvas, sf, textures);
+                let transform = Transform { scale: obj.get_scale(), alpha: obj.get_alpha(), ..Transform::at(screen_coords.0, screen_coords.1) };
+                render::draw_transformed_texture(&transform, &asset_data, canvas, sf, textures);
             }
         }
 
-        // get the current screen
-        let scrn = &mut self.current_screen;
-        // make sure the screen isn't None, and render it to screen
-        if scrn.is_some() {
-            scrn.as_mut().unwrap().render(textures, sf, canvas, dims, self.debug);
+        // render the screens from the top one that isn't an overlay upwards, so overlays show over what is below them
+        let first = self.screens.iter().rposition(|screen| !screen.is_overlay()).unwrap_or(0);
+        for screen in self.screens.iter_mut().skip(first) {
+            screen.render(textures, sf, canvas, dims, self.debug);
+        }
+
+        // the transition goes over everything in the game area
+        if let Some(transition) = &self.transition {
+            transition.render(canvas, sf);
         }
 
+        // cover anything drawn outside the game area
+        render::draw_bars(canvas, sf);
+
         // draw the mouse, unless instructed otherwise
         if self.draw_mouse {
             if self.use_finger {
@@ -344,14 +720,23 @@ impl Game {
 
     }
 
+    /// Keep the files that couldn't be loaded, showing them on the error screen if there are any
+    pub fn show_load_errors(&mut self, errors : Vec<LoadError>) {
+        self.load_errors = errors;
+        if !self.load_errors.is_empty() {
+            let screen = LoadErrorScreen::create(self);
+            self.push_screen(screen);
+        }
+    }
+
     /// Plays a sound file given a [`ResourceLocation`]
     pub fn play_sound(&self, resource_location : ResourceLocation) {
         // get sound from mao
-        let sound  = self.sounds.get(&resource_location.to_string());
+        let sound  = self.assets.sounds.get(&resource_location);
         // if the sound exists, play it
         if sound.is_some() {
             #[cfg(not(target_os = "emscripten"))]
-            self.audio_manager.play_sound(sound.unwrap())
+            self.audio_manager.play_sound(sound.unwrap(), self.settings.effect_volume())
         }
         // else, warn in the logs.
         else {
@@ -370,9 +755,10 @@ impl Game {
             held_keys : vec![],
             running : true,
             current_level : None,
-            current_screen : None,
-            tiles: Default::default(),
-            sounds : Default::default(),
+            screens : vec![],
+            screen_commands : vec![],
+            transition : None,
+            assets: Assets::default(),
             draw_mouse : true,
             sf : 6,
             use_finger : false,
@@ -380,21 +766,38 @@ impl Game {
             score: 0.0,
             debug : false,
             mouse: (0, 0),
-            cards: Default::default(),
-            selected_count: 0,
-            selected_cards : (Card::empty(), Card::empty()),
-            current_turn : Turn::Player1,
+            deck: None,
+            pair_count: None,
+            player_count: 2,
+            players: vec![],
+            computer: None,
+            menu_choices: None,
+            seed: None,
+            match_seed: 0,
+            rng: StdRng::seed_from_u64(0),
+            recording: Replay::create(0, None, None, 2),
+            playback: None,
+            playback_position: 0,
+            stats: Stats::empty(),
+            settings: Settings::default(),
+            resume: None,
+            load_errors: vec![],
+            reload_assets: false,
+            streak: 0,
+            best_streak: 0,
+            rules : MatchRules::new(vec![], 2),
             wait_timer : -1.0,
-            prev_success: false,
-            player_1_score : 0,
-            player_2_score : 0,
+            deal_timer : 0.0,
+            paused : false,
+            typing : false,
             audio_manager : AudioManager::create()
         }
         
     }
 
-    pub fn get_turn(&mut self) -> Turn {
-        self.current_turn.clone()
+    /// The player whose turn it is, counted from 0
+    pub fn get_turn(&mut self) -> usize {
+        self.rules.current_turn()
     }
     
 }
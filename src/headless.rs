@@ -0,0 +1,64 @@
+use log::{info, warn};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use crate::ai::{AiPlayer, Difficulty};
+use crate::assets::Assets;
+use crate::cli::Args;
+use crate::deck;
+use crate::rules::{MatchRules, Outcome};
+
+/// Play a whole match between computer players on the [`MatchRules`] alone, with no window, sound or delays, and print how it went.
+/// The board is dealt the same way as in the game, so a seed gives the same board in both
+pub fn run(args : &Args, assets : &Assets) {
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let chosen_deck = args.deck.as_ref().and_then(|rl| {
+        let deck = assets.decks.find(rl);
+        if deck.is_none() {
+            warn!("There is no deck called {}, using every card", rl);
+        }
+        deck
+    });
+    let board = deck::deal(chosen_deck, &assets.cards, None, &mut rng);
+    if board.is_empty() {
+        println!("No cards to play with, check the data directory");
+        return
+    }
+
+    let players = args.players.unwrap_or(2);
+    let mut rules = MatchRules::new(board, players);
+    let mut bots = (0..players).map(|_| AiPlayer::create(Difficulty::Normal)).collect::<Vec<_>>();
+    info!("Playing a headless match with {} players on seed {}", players, seed);
+
+    while !rules.is_over() {
+        let index = match bots[rules.current_turn()].choose(&rules, &mut rng) {
+            Some(index) => {index}
+            None => {break}
+        };
+        if let Outcome::Rejected(rejection) = rules.flip(index) {
+            warn!("Player {} tried to flip card {}: {:?}", rules.current_turn() + 1, index, rejection);
+            break
+        }
+        for bot in &mut bots {
+            bot.observe(index, &rules);
+        }
+        if rules.pair_pending() {
+            rules.resolve();
+            for bot in &mut bots {
+                bot.end_turn(&rules, &mut rng);
+            }
+        }
+    }
+
+    println!("seed: {}", seed);
+    println!("flips: {}", rules.flips());
+    for player in 0..players {
+        println!("player {}: {} pairs", player + 1, rules.score(player));
+    }
+    match rules.winner() {
+        Some(winner) if !rules.is_solo() => {println!("winner: player {}", winner + 1)}
+        Some(_) => {}
+        None => {println!("winner: draw")}
+    }
+}
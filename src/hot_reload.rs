@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, Instant, SystemTime};
+use log::{info, warn};
+use sdl2::render::{Texture, TextureCreator};
+use sdl2::video::WindowContext;
+use crate::assets::{Assets, DataLoader, Registry};
+use crate::loader;
+use crate::packs::{PackFile, ResourcePack};
+
+// how often the folders are checked for changes
+const POLL_INTERVAL : Duration = Duration::from_secs(1);
+
+/// Watches the assets and data folders, and the enabled resource packs that are folders, while the game runs with `--dev`, loading files again as soon as they are saved.
+/// Packs keep their priority, so a changed file is only loaded if no later pack replaces it. Packs in zip archives aren't watched
+pub struct HotReloader {
+    packs : Vec<ResourcePack>, // the built in files and the enabled packs, in the order they are loaded
+    loaders : Vec<Box<dyn DataLoader>>,
+    modified : HashMap<String, SystemTime>, // when each file was last changed, by path
+    last_poll : Instant
+}
+
+impl HotReloader {
+    /// Start watching the same packs the game was loaded from. Call it again when the enabled packs change
+    pub fn create(assets_dir : &str, data_dir : &str, enabled : &[String]) -> Self {
+        // packs that can't be opened were already reported when the game loaded them
+        let mut ret = Self {
+            packs: ResourcePack::stack(assets_dir, data_dir, enabled, &mut vec![]),
+            loaders: Assets::loaders(),
+            modified: HashMap::new(),
+            last_poll: Instant::now()
+        };
+        // everything has just been loaded, so only note the times
+        ret.changed();
+        info!("Watching {}, {} and {} resource packs for changes", assets_dir, data_dir, enabled.len());
+        ret
+    }
+
+    // every file that is new or has been saved since the last check, along with the index of the pack it is in.
+    // A file replaced by one in a later pack isn't in use, so it is left out
+    fn changed(&mut self) -> Vec<(usize, PackFile)> {
+        let mut in_use = HashMap::new(); // resource location to the last pack that has it
+        let mut changed = vec![];
+        for (index, pack) in self.packs.iter().enumerate() {
+            let mut files = pack.assets(".png");
+            files.extend(pack.assets(".ogg"));
+            files.extend(pack.data());
+
+            for file in files {
+                in_use.insert(file.resource_location.to_string(), index);
+                // files in a zip archive aren't on disk, so they never show as changed
+                let modified = match fs::metadata(&file.path).and_then(|metadata| metadata.modified()) {
+                    Ok(modified) => {modified}
+                    Err(_) => {continue}
+                };
+                if self.modified.insert(file.path.clone(), modified) != Some(modified) {
+                    changed.push((index, file));
+                }
+            }
+        }
+        changed.into_iter().filter(|(index, file)| in_use.get(&file.resource_location.to_string()) == Some(index)).collect()
+    }
+
+    /// Check for changed files, at most once a [`POLL_INTERVAL`], and load them into the live registries.
+    /// A texture that can't be loaded any more is taken out, so `game:missing.png` is drawn in its place
+    pub fn update<'a>(&mut self, assets : &mut Assets, textures : &mut Registry<Texture<'a>>, texture_creator : &'a TextureCreator<WindowContext>) {
+        if self.last_poll.elapsed() < POLL_INTERVAL {
+            return
+        }
+        self.last_poll = Instant::now();
+
+        for (index, file) in self.changed() {
+            let pack = &self.packs[index];
+            let resource_location = file.resource_location.to_string();
+            if file.path.to_lowercase().ends_with(".png") {
+                match loader::load_texture(pack, &file, texture_creator) {
+                    Ok(texture) => {
+                        info!("Reloaded texture : {}", resource_location);
+                        textures.insert(file.resource_location, texture);
+                    }
+                    Err(e) => {
+                        warn!("Could not reload {}, showing the missing texture", e);
+                        textures.remove(&file.resource_location);
+                    }
+                }
+            }
+            else if file.path.to_lowercase().ends_with(".ogg") {
+                match loader::load_sound(pack, &file) {
+                    Ok(sound) => {
+                        info!("Reloaded sound : {}", resource_location);
+                        assets.sounds.insert(file.resource_location, sound);
+                    }
+                    Err(e) => {warn!("Could not reload {}, keeping the old sound", e)}
+                }
+            }
+            else {
+                // broken data keeps the last version that loaded, so a half saved file doesn't lose anything
+                match assets.load_data_file(pack, &file, &self.loaders) {
+                    Ok(Some(data_type)) => {info!("Reloaded {} : {}", data_type, resource_location)}
+                    Ok(None) => {}
+                    Err(e) => {warn!("Could not reload {}, keeping the old version", e)}
+                }
+            }
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use image::imageops::tile;
 use log::warn;
 use sdl2::render::{Canvas, Texture, WindowCanvas};
+use crate::assets::Registry;
 use crate::game::Game;
 use crate::resource_location::ResourceLocation;
 use crate::tile::{Tile, TileSize, TileType};
@@ -23,7 +24,7 @@ impl Level {
     /// Calls the [`render`] function on all the tile graphs
     ///
     /// [`render`]: TileGraph::render
-    pub fn render(&mut self, player_coords :  (f32, f32), texture : &HashMap<String, Texture>, canvas: &mut WindowCanvas, sf : i32, debug : bool) {
+    pub fn render(&mut self, player_coords :  (f32, f32), texture : &Registry<Texture>, canvas: &mut WindowCanvas, sf : i32, debug : bool) {
         self.tile_big.render(player_coords, texture, canvas, sf);
         self.tile_medium.render(player_coords, texture, canvas, sf);
         self.tile_small.render(player_coords, texture, canvas, sf);
@@ -51,7 +52,7 @@ impl Level {
     }
 
     /// Creates the level for the demo, entities are loaded @ [`Game::load_demo_level`]
-    pub fn create_scene_background(tiles : &HashMap<String, Tile>) -> Self {
+    pub fn create_scene_background(tiles : &Registry<Tile>) -> Self {
 
         // get tiles from tile map
 
@@ -126,7 +127,7 @@ impl TileGraph {
     }
 
     /// Renders the tile to screen
-    pub fn render(&mut self, player_coords :  (f32, f32), texture : &HashMap<String, Texture>, canvas: &mut WindowCanvas, sf : i32) {
+    pub fn render(&mut self, player_coords :  (f32, f32), texture : &Registry<Texture>, canvas: &mut WindowCanvas, sf : i32) {
         // get scale
         let tile_scale = self.tile_size.get().0 as i32;
 
@@ -0,0 +1,412 @@
+use std::fmt;
+use std::path::Path;
+use log::{info, warn};
+use sdl2::rect::Rect;
+use sdl2::image::LoadTexture;
+use sdl2::render::{Texture, TextureCreator};
+use sdl2::video::WindowContext;
+use crate::assets::Registry;
+use crate::animation::{Animation, Frame, LoopMode};
+use crate::deck::Deck;
+use crate::packs::{PackFile, ResourcePack};
+use crate::entities::card_entity::Card;
+use crate::resource_location::ResourceLocation;
+use crate::sound::Sound;
+use crate::tile::{Tile, TileSize, TileType};
+
+/// Why a file couldn't be loaded. Broken files are skipped rather than stopping the game, and these are collected to be shown on the [`LoadErrorScreen`]
+///
+/// [`LoadErrorScreen`]: crate::screens::load_error_screen::LoadErrorScreen
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file or folder couldn't be read
+    Io { file : String, reason : String },
+    /// The file isn't valid JSON
+    InvalidJson { file : String },
+    /// A field the file needs isn't there
+    MissingField { file : String, field : String },
+    /// A field holds the wrong type of value
+    WrongType { file : String, field : String, expected : &'static str },
+    /// The image couldn't be turned into a texture
+    Texture { file : String, reason : String }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io { file, reason } => {write!(f, "{}: could not be read ({})", file, reason)}
+            LoadError::InvalidJson { file } => {write!(f, "{}: is not valid JSON", file)}
+            LoadError::MissingField { file, field } => {write!(f, "{}: \"{}\" is missing", file, field)}
+            LoadError::WrongType { file, field, expected } => {write!(f, "{}: \"{}\" should be {}", file, field, expected)}
+            LoadError::Texture { file, reason } => {write!(f, "{}: could not be loaded as a texture ({})", file, reason)}
+        }
+    }
+}
+
+/// A data file read into memory, with typed access to its fields
+pub struct JsonFile {
+    file : String,
+    json : String
+}
+
+impl JsonFile {
+
+    /// Read a file's contents, checking it is valid JSON. `file` is where it came from, for errors
+    pub fn parse(file : String, bytes : Vec<u8>) -> Result<Self, LoadError> {
+        let json = match String::from_utf8(bytes) {
+            Ok(json) if gjson::valid(&json) => {json}
+            _ => {return Err(LoadError::InvalidJson { file })}
+        };
+        Ok(Self { file, json })
+    }
+
+    /// The `"type"` of data in the file, which picks the loader that reads it
+    pub fn data_type(&self) -> String {
+        self.get("type").to_string()
+    }
+
+    fn get<'a>(&'a self, field : &'a str) -> gjson::Value<'a> {
+        gjson::get(&self.json, field)
+    }
+
+    /// Whether the file has a field
+    pub fn has(&self, field : &str) -> bool {
+        self.get(field).exists()
+    }
+
+    // the value of a field, or the error for it missing
+    fn value<'a>(&'a self, field : &'a str) -> Result<gjson::Value<'a>, LoadError> {
+        let value = self.get(field);
+        if value.exists() {
+            Ok(value)
+        }
+        else {
+            Err(LoadError::MissingField { file: self.file.clone(), field: field.to_string() })
+        }
+    }
+
+    fn wrong_type(&self, field : &str, expected : &'static str) -> LoadError {
+        LoadError::WrongType { file: self.file.clone(), field: field.to_string(), expected }
+    }
+
+    /// A string field
+    pub fn string(&self, field : &str) -> Result<String, LoadError> {
+        let value = self.value(field)?;
+        if value.kind() != gjson::Kind::String {
+            return Err(self.wrong_type(field, "a string"))
+        }
+        Ok(value.to_string())
+    }
+
+    /// A whole number field that can't be negative
+    pub fn u32(&self, field : &str) -> Result<u32, LoadError> {
+        self.value(field)?.json().parse::<u32>().map_err(|_| self.wrong_type(field, "a whole number, 0 or more"))
+    }
+
+    /// A whole number field
+    pub fn i32(&self, field : &str) -> Result<i32, LoadError> {
+        self.value(field)?.json().parse::<i32>().map_err(|_| self.wrong_type(field, "a whole number"))
+    }
+
+    /// A number field, which can have a fraction
+    pub fn f32(&self, field : &str) -> Result<f32, LoadError> {
+        let value = self.value(field)?;
+        if value.kind() != gjson::Kind::Number {
+            return Err(self.wrong_type(field, "a number"))
+        }
+        Ok(value.f32())
+    }
+
+    /// How many entries a list field has
+    pub fn count(&self, field : &str) -> Result<usize, LoadError> {
+        let value = self.value(field)?;
+        if value.kind() != gjson::Kind::Array {
+            return Err(self.wrong_type(field, "a list"))
+        }
+        Ok(value.array().len())
+    }
+
+    /// A true or false field
+    pub fn bool(&self, field : &str) -> Result<bool, LoadError> {
+        match self.value(field)?.kind() {
+            gjson::Kind::True => {Ok(true)}
+            gjson::Kind::False => {Ok(false)}
+            _ => {Err(self.wrong_type(field, "true or false"))}
+        }
+    }
+
+    /// A field holding a resource location, like `namespace:path`
+    pub fn resource_location(&self, field : &str) -> Result<ResourceLocation, LoadError> {
+        ResourceLocation::try_parse(&self.string(field)?).ok_or_else(|| self.wrong_type(field, "a resource location, like namespace:path"))
+    }
+
+    /// A list of strings, which is empty if the field isn't there
+    pub fn strings(&self, field : &str) -> Result<Vec<String>, LoadError> {
+        let value = self.get(field);
+        if !value.exists() {
+            return Ok(vec![])
+        }
+        if value.kind() != gjson::Kind::Array || value.array().iter().any(|v| v.kind() != gjson::Kind::String) {
+            return Err(self.wrong_type(field, "a list of strings"))
+        }
+        Ok(value.array().iter().map(|v| v.to_string()).collect())
+    }
+}
+
+/// The part of `path` inside `root`, written with forward slashes like the path of a [`ResourceLocation`]
+pub fn relative_path(root : &Path, path : &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace("\\", "/")
+}
+
+/// Load every image in the packs as a texture, later packs replacing the textures of earlier ones
+pub fn load_textures<'a>(packs : &[ResourcePack], texture_creator : &'a TextureCreator<WindowContext>, errors : &mut Vec<LoadError>) -> Registry<Texture<'a>> {
+    info!("Loading textures...");
+    let mut textures = Registry::new();
+    for pack in packs {
+        for file in pack.assets(".png") {
+            match load_texture(pack, &file, texture_creator) {
+                Ok(texture) => {
+                    info!("Loaded texture : {}", file.resource_location.to_string());
+                    textures.insert(file.resource_location, texture);
+                }
+                Err(e) => {report(errors, e)}
+            }
+        }
+    }
+    info!("{} textures loaded!", textures.len());
+    textures
+}
+
+/// Load one image from a pack as a texture
+pub fn load_texture<'a>(pack : &ResourcePack, file : &PackFile, texture_creator : &'a TextureCreator<WindowContext>) -> Result<Texture<'a>, LoadError> {
+    let bytes = pack.read(file)?;
+    texture_creator.load_texture_bytes(&bytes).map_err(|reason| LoadError::Texture { file: file.path.clone(), reason })
+}
+
+/// Load one sound from a pack
+pub fn load_sound(pack : &ResourcePack, file : &PackFile) -> Result<Sound, LoadError> {
+    let bytes = pack.read(file)?;
+    Ok(Sound { data: bytes.into(), resource_location: file.resource_location.clone() })
+}
+
+/// Load every sound in the packs, later packs replacing the sounds of earlier ones
+pub fn load_sounds(packs : &[ResourcePack], errors : &mut Vec<LoadError>) -> Registry<Sound> {
+    info!("Loading sounds...");
+    let mut sounds = Registry::new();
+    for pack in packs {
+        for file in pack.assets(".ogg") {
+            match load_sound(pack, &file) {
+                Ok(sound) => {
+                    info!("Loaded sound : {}", file.resource_location.to_string());
+                    sounds.insert(file.resource_location, sound);
+                }
+                Err(e) => {report(errors, e)}
+            }
+        }
+    }
+    info!("{} sounds loaded!", sounds.len());
+    sounds
+}
+
+/// Log a problem and keep it for the error screen
+pub fn report(errors : &mut Vec<LoadError>, error : LoadError) {
+    warn!("Skipped {}", error);
+    errors.push(error);
+}
+
+/// Read a tile data file, for the entirely data driven tile system
+pub fn parse_tile(file : &JsonFile, resource_location : ResourceLocation) -> Result<Tile, LoadError> {
+    // "name" : string
+    let name = file.string("name")?;
+
+    // "resource_location" : string
+    let texture = file.resource_location("resource_location")?;
+
+    // "uv" {"x" : int,  "y" : int}
+    let uv = (file.u32("uv.x")?, file.u32("uv.y")?);
+
+    // "type" : string    - might remove this bit as it may not be needed
+    let ttype = TileType::parse(file.string("type")?);
+
+    // "size" : string
+    let size = TileSize::parse(&file.string("size")?);
+
+    // "origin" {"x" : int ,  "y" :  int }  - at the moment it refers to where the centre point of the sprite is,
+    //                                  but will be changed to be the centre point of the hitbox, as the sprites
+    //                                  should all have the origin of (0,0) to render correctly
+    let origin = (file.i32("origin.x")?, file.i32("origin.y")?);
+
+    // "collision" : bool
+    let collision = file.bool("collision")?;
+
+    // "collision_box" {"x" : int ,  "y" :  int }
+    let collision_box = if collision {
+        Some((file.u32("collision_box.x")?, file.u32("collision_box.y")?))
+    }
+    else {
+        None
+    };
+
+    Ok(Tile::create(name, resource_location, texture, uv, ttype, size, origin, collision, collision_box))
+}
+
+/// Read a card data file, for the entirely data driven card system
+pub fn parse_card(file : &JsonFile, resource_location : ResourceLocation) -> Result<Card, LoadError> {
+    // "name" : string
+    let name = file.string("name")?;
+
+    // "resource_location" : string
+    let texture = file.resource_location("resource_location")?;
+
+    // "tags" : [string] - optional, used by decks to pick up cards
+    let tags = file.strings("tags")?;
+
+    Ok(Card::create(name, resource_location, texture, tags))
+}
+
+/// Read a deck data file, decks pick a set of cards to play a match with
+pub fn parse_deck(file : &JsonFile, resource_location : ResourceLocation) -> Result<Deck, LoadError> {
+    // "name" : string
+    let name = file.string("name")?;
+
+    // "cards" : [string] - resource locations of card data files
+    let mut cards = vec![];
+    for rl in file.strings("cards")? {
+        cards.push(ResourceLocation::try_parse(&rl).ok_or_else(|| file.wrong_type("cards", "a list of resource locations, like namespace:path"))?);
+    }
+
+    // "tags" : [string] - every card with one of these tags is added
+    let tags = file.strings("tags")?;
+
+    // "pairs" : int - optional, defaults to every card in the deck
+    let pairs = if file.has("pairs") {Some(file.u32("pairs")?)} else {None};
+
+    // "card_back" : string - optional, a texture or an animation data file
+    let card_back = if file.has("card_back") {
+        file.resource_location("card_back")?
+    }
+    else {
+        ResourceLocation::new("memory_game", "animations/card_back.json")
+    };
+
+    Ok(Deck::create(name, resource_location, cards, tags, pairs, card_back))
+}
+
+/// Read an animation data file, the frames of a sprite animation cut from one texture
+pub fn parse_animation(file : &JsonFile, _resource_location : ResourceLocation) -> Result<Animation, LoadError> {
+    // "texture" : string - the sheet the frames are on
+    let texture = file.resource_location("texture")?;
+
+    // "origin" {"x" : int, "y" : int} - optional, the centre point of every frame
+    let origin = if file.has("origin") {(file.i32("origin.x")?, file.i32("origin.y")?)} else {(0, 0)};
+
+    // "loop" : string - optional, "once", "loop" or "ping_pong", defaults to "loop"
+    let loop_mode = if file.has("loop") {
+        LoopMode::parse(&file.string("loop")?).ok_or_else(|| file.wrong_type("loop", "\"once\", \"loop\" or \"ping_pong\""))?
+    }
+    else {
+        LoopMode::Loop
+    };
+
+    // "frames" : [{"x" : int, "y" : int, "width" : int, "height" : int, "duration" : number, "texture" : string}] - the duration is in seconds.
+    // The texture is optional, for a frame cut from a different texture to the rest
+    let mut frames = vec![];
+    for i in 0..file.count("frames")? {
+        let field = |name : &str| format!("frames.{}.{}", i, name);
+        let uv = Rect::new(file.i32(&field("x"))?, file.i32(&field("y"))?, file.u32(&field("width"))?, file.u32(&field("height"))?);
+        let duration = file.f32(&field("duration"))?;
+        if duration < 0.0 {
+            return Err(file.wrong_type(&field("duration"), "0 or more"))
+        }
+        let texture = if file.has(&field("texture")) {Some(file.resource_location(&field("texture"))?)} else {None};
+        frames.push(Frame { uv, duration, texture });
+    }
+    if frames.is_empty() {
+        return Err(file.wrong_type("frames", "a list with at least one frame"))
+    }
+
+    Ok(Animation::create(texture, origin, frames, loop_mode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE : &str = "data/test/cards/apple.json";
+
+    fn json(text : &str) -> JsonFile {
+        JsonFile::parse(FILE.to_string(), text.as_bytes().to_vec()).unwrap()
+    }
+
+    fn card(text : &str) -> Result<Card, LoadError> {
+        parse_card(&json(text), ResourceLocation::new("test", "cards/apple.json"))
+    }
+
+    fn deck(text : &str) -> Result<Deck, LoadError> {
+        parse_deck(&json(text), ResourceLocation::new("test", "decks/fruit.json"))
+    }
+
+    fn animation(text : &str) -> Result<Animation, LoadError> {
+        parse_animation(&json(text), ResourceLocation::new("test", "animations/shine.json"))
+    }
+
+    // the field a missing field or wrong type error is about, checking it names the file
+    fn missing(error : LoadError) -> String {
+        match error {
+            LoadError::MissingField { file, field } if file == FILE => {field}
+            e => {panic!("expected a missing field in {}, got {:?}", FILE, e)}
+        }
+    }
+
+    fn wrong_type(error : LoadError) -> String {
+        match error {
+            LoadError::WrongType { file, field, .. } if file == FILE => {field}
+            e => {panic!("expected a wrong type in {}, got {:?}", FILE, e)}
+        }
+    }
+
+    #[test]
+    fn malformed_json() {
+        for text in ["{\"type\" : \"card\",", "not json", "{\"name\" : }", ""] {
+            match JsonFile::parse(FILE.to_string(), text.as_bytes().to_vec()) {
+                Err(LoadError::InvalidJson { file }) => {assert_eq!(file, FILE)}
+                _ => {panic!("{:?} should be invalid JSON", text)}
+            }
+        }
+        assert!(matches!(JsonFile::parse(FILE.to_string(), vec![0xff, 0xfe]), Err(LoadError::InvalidJson { .. })));
+    }
+
+    #[test]
+    fn valid_files() {
+        let apple = card("{\"type\" : \"card\", \"name\" : \"Apple\", \"resource_location\" : \"test:cards/apple.png\", \"tags\" : [\"fruit\"]}").unwrap();
+        assert_eq!(apple.get_name(), "Apple");
+        assert_eq!(apple.get_texture_location().to_string(), "test:cards/apple.png");
+        let fruit = deck("{\"type\" : \"deck\", \"name\" : \"Fruit\", \"cards\" : [\"test:cards/apple.json\"], \"pairs\" : 4}").unwrap();
+        assert_eq!(fruit.get_card_back().to_string(), "memory_game:animations/card_back.json");
+        let shine = animation("{\"texture\" : \"test:shine.png\", \"loop\" : \"once\", \"frames\" : [{\"x\" : 0, \"y\" : 0, \"width\" : 4, \"height\" : 4, \"duration\" : 0.5}]}").unwrap();
+        assert_eq!(shine.asset_data(0.0).resource_location.to_string(), "test:shine.png");
+    }
+
+    #[test]
+    fn missing_fields() {
+        assert_eq!(missing(card("{\"resource_location\" : \"test:cards/apple.png\"}").err().unwrap()), "name");
+        assert_eq!(missing(card("{\"name\" : \"Apple\"}").err().unwrap()), "resource_location");
+        assert_eq!(missing(deck("{}").err().unwrap()), "name");
+        assert_eq!(missing(animation("{\"frames\" : []}").err().unwrap()), "texture");
+        assert_eq!(missing(animation("{\"texture\" : \"test:shine.png\"}").err().unwrap()), "frames");
+        assert_eq!(missing(animation("{\"texture\" : \"test:shine.png\", \"frames\" : [{\"x\" : 0, \"y\" : 0, \"width\" : 4, \"duration\" : 1}]}").err().unwrap()), "frames.0.height");
+    }
+
+    #[test]
+    fn wrongly_typed_fields() {
+        assert_eq!(wrong_type(card("{\"name\" : 3, \"resource_location\" : \"test:cards/apple.png\"}").err().unwrap()), "name");
+        assert_eq!(wrong_type(card("{\"name\" : \"Apple\", \"resource_location\" : \"no namespace\"}").err().unwrap()), "resource_location");
+        assert_eq!(wrong_type(card("{\"name\" : \"Apple\", \"resource_location\" : \"test:a.png\", \"tags\" : \"fruit\"}").err().unwrap()), "tags");
+        assert_eq!(wrong_type(deck("{\"name\" : \"Fruit\", \"pairs\" : -2}").err().unwrap()), "pairs");
+        assert_eq!(wrong_type(deck("{\"name\" : \"Fruit\", \"cards\" : [\"apple\"]}").err().unwrap()), "cards");
+        assert_eq!(wrong_type(animation("{\"texture\" : \"test:shine.png\", \"loop\" : \"sometimes\", \"frames\" : []}").err().unwrap()), "loop");
+        assert_eq!(wrong_type(animation("{\"texture\" : \"test:shine.png\", \"frames\" : {}}").err().unwrap()), "frames");
+        assert_eq!(wrong_type(animation("{\"texture\" : \"test:shine.png\", \"frames\" : []}").err().unwrap()), "frames");
+        assert_eq!(wrong_type(animation("{\"texture\" : \"test:shine.png\", \"frames\" : [{\"x\" : 0, \"y\" : 0, \"width\" : 4, \"height\" : 4, \"duration\" : \"long\"}]}").err().unwrap()), "frames.0.duration");
+    }
+}
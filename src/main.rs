@@ -11,10 +11,26 @@ mod widgets;
 mod screens;
 mod widget;
 mod sound;
+mod rules;
+mod board;
+mod deck;
+mod player;
+mod ai;
+mod replay;
+mod stats;
+mod save;
+mod settings;
+mod cli;
+mod headless;
+mod loader;
+mod assets;
+mod archive;
+mod packs;
+mod hot_reload;
+mod tween;
+mod animation;
+mod transition;
 
-use crate::sound::Sound;
-use std::collections::HashMap;
-use std::{env, fs};
 use std::fs::File;
 use std::hash::Hash;
 use std::io::BufReader;
@@ -26,22 +42,24 @@ use crate::game::Game;
 use num::clamp;
 use render::TARGET_DIMENSIONS;
 use sdl2::event::Event;
-use sdl2::image::LoadTexture;
 use sdl2::keyboard::Keycode;
 use sdl2::rect::Rect;
 use std::time::Instant;
 use gjson::Value;
 use log::info;
 use sdl2::event::Event::KeyDown;
-use sdl2::render::Texture;
-use walkdir::WalkDir;
-use resource_location::ResourceLocation;
+use sdl2::render::WindowCanvas;
+use sdl2::event::WindowEvent;
+use sdl2::pixels::Color;
+use sdl2::video::{FullscreenType, WindowPos};
 use widget::Widget;
-use crate::entities::card_entity::{Card, CardEntity};
+use resource_location::ResourceLocation;
+use crate::assets::Assets;
+use crate::packs::ResourcePack;
+use crate::hot_reload::HotReloader;
 use crate::level::Level;
 use crate::render::AssetData;
 use crate::screen::Screen;
-use crate::tile::{Tile, TileSize, TileType};
 use crate::widget::Alignment;
 use crate::screens::main_menu_screen::MainMenuScreen;
 
@@ -59,317 +77,164 @@ macro_rules! info {
     }};
 }
 
+/// The size of the window when not in fullscreen, at the scale from the settings or the biggest that leaves room around it on the screen
+fn windowed_size(scale : Option<u32>, display : (u32, u32)) -> (u32, u32) {
+    let scale = match scale {
+        Some(scale) => {scale.max(1)}
+        None => {(display.0 / TARGET_DIMENSIONS.0).min(display.1 / TARGET_DIMENSIONS.1).max(2) - 1}
+    };
+    (TARGET_DIMENSIONS.0 * scale, TARGET_DIMENSIONS.1 * scale)
+}
+
+/// Work out the viewport for the current window size, returning the new scale factor
+fn fit_viewport(canvas : &WindowCanvas, max_scale : Option<u32>) -> i32 {
+    let size = canvas.output_size().unwrap_or(TARGET_DIMENSIONS);
+    let viewport = render::Viewport::fit(size, max_scale);
+    let scale = viewport.get_scale();
+    info!("Window is {}x{}, drawing at {}x scale", size.0, size.1, scale);
+    *render::get_viewport().lock().unwrap() = viewport;
+    scale
+}
+
+/// Switch the window between fullscreen and windowed
+fn apply_display(canvas : &mut WindowCanvas, (fullscreen, scale) : (bool, Option<u32>), display : (u32, u32)) {
+    let window = canvas.window_mut();
+    if fullscreen {
+        let _ = window.set_fullscreen(FullscreenType::Desktop);
+    }
+    else {
+        let _ = window.set_fullscreen(FullscreenType::Off);
+        let size = windowed_size(scale, display);
+        let _ = window.set_size(size.0, size.1);
+        window.set_position(WindowPos::Centered, WindowPos::Centered);
+    }
+    window.set_mouse_grab(fullscreen);
+}
+
 fn main() {
 
     // initial set up
 
-    // start logger
-    utils::init_logger();
+    // read the command line, then start the logger at the level it asks for
+    let args = cli::Args::from_env();
+    utils::init_logger(args.log_level);
+    args.report();
+
+    // load the settings, they decide which resource packs are used and how the window is set up. Display options on the command line take priority
+    let mut settings = settings::Settings::load();
+    if args.windowed {
+        settings.fullscreen = false;
+    }
+    if args.scale.is_some() {
+        settings.scale = args.scale;
+    }
+
+    // data files and sounds don't need a window, so they are loaded first. Broken files are skipped and listed once the game starts
+    let mut load_errors = vec![];
+    let packs = ResourcePack::stack(&args.assets_dir, &args.data_dir, &settings.packs, &mut load_errors);
+    let assets = Assets::load(&packs, &mut load_errors);
+    if assets.cards.is_empty() {
+        log::warn!("No cards were loaded from {}, there is nothing to play with", args.data_dir);
+    }
+
+    // --headless plays a match between computer players and exits before SDL is started
+    if args.headless {
+        headless::run(&args, &assets);
+        return
+    }
 
     info!("Initialising SDL2");
 
     // start SDL2
     let sdl_ctx = sdl2::init().unwrap();
-    let scale_factor =
-        sdl_ctx.video().unwrap().current_display_mode(0).unwrap().w / TARGET_DIMENSIONS.0 as i32;
+    let display = sdl_ctx.video().unwrap().current_display_mode(0).unwrap();
     let video_subsys = sdl_ctx.video().unwrap();
 
-    // the actual dimensions of the game screen, as they won't always be 320/180 due to differing aspect ratios
-    let dims = ((sdl_ctx.video().unwrap().current_display_mode(0).unwrap().w / scale_factor) as u32, (sdl_ctx.video().unwrap().current_display_mode(0).unwrap().h / scale_factor) as u32);
-
-    println!("{:?}", dims);
+    // the game is always drawn at the target size, with black bars around it if the window is a different shape
+    let dims = TARGET_DIMENSIONS;
     render::get_actual_dimensions().lock().unwrap().set(dims);
 
-    let scale_offset = (sdl_ctx.video().unwrap().current_display_mode(0).unwrap().h / scale_factor as i32) - 180;
-    //todo : get the difference in height and display 2 black bars to give 16:9 ratio screen
-
     //hide mouse
     sdl_ctx.mouse().show_cursor(false);
 
-    // create window
+    // create window, a window starts at the scale from the settings or the biggest that leaves room around it on the screen
+    let window_size = windowed_size(settings.scale, (display.w as u32, display.h as u32));
+    let mut window_builder = video_subsys.window("Memory Game", window_size.0, window_size.1);
     #[cfg(not(target_os = "emscripten"))]
-    let mut window = video_subsys
-        .window("Memory Game", TARGET_DIMENSIONS.0, TARGET_DIMENSIONS.1)
-        .vulkan()
-        .fullscreen_desktop()
-        .build()
-        .unwrap();
-
-    // create window
+    window_builder.vulkan();
     #[cfg(target_os = "emscripten")]
-    let mut window = video_subsys
-        .window("Memory Game", TARGET_DIMENSIONS.0, TARGET_DIMENSIONS.1)
-        .opengl() // emscripten doesnt support vulkan
-        .fullscreen_desktop()
-        .build()
-        .unwrap();
+    window_builder.opengl(); // emscripten doesnt support vulkan
+    window_builder.resizable().position_centered();
+    if settings.fullscreen {
+        window_builder.fullscreen_desktop();
+    }
+    let mut window = window_builder.build().unwrap();
 
     info!("complete");
 
-    // lock mouse to window
-    &window.set_mouse_grab(true);
+    // lock mouse to window, unless it is in a window where that would get in the way
+    window.set_mouse_grab(settings.fullscreen);
 
     //create canvas
     let mut canvas = &mut window.into_canvas().build().unwrap();
+
+    // fit the game into the window
+    let mut displayed = (settings.fullscreen, settings.scale);
+    let mut scale_factor = fit_viewport(canvas, settings.scale);
     let texture_creator = canvas.texture_creator();
 
-    // counter to count how many objects are loaded for the debug logs
-    let mut counter = 0;
+    // load textures now there is a window to make them for
+    let mut textures = loader::load_textures(&packs, &texture_creator, &mut load_errors);
 
-    // initialise textures - new
-    info!("Loading textures...");
-    // create hashmap
-    let mut textures : HashMap<String, Texture> = HashMap::new();
-    // iterate through the assets directory
-    for dir in WalkDir::new("./assets/") {
-        let mut path = String::from(dir.unwrap().path().to_str().unwrap()).replace("\\", "/");
-        // if the file is an image, save it - in future there will likely be a hashmap for other files, like animation data or other bits idk yet
-        if path.clone().to_lowercase().ends_with(".png") {
-            // create the resource location
-            let mut rl = ResourceLocation::empty();
+    // register event pump to handle inputs
+    let mut event_pump = sdl_ctx.event_pump().unwrap();
 
-            // split the path by \s
-            let split : Vec<_> = path.split("/").collect();
+    canvas.present();
 
-            // name space is in ./assets/>>namespace<<, so it is the third element in the list
-            let namespace = &split[2];
-            rl.set_namespace(namespace.to_string());
+    info!("Initiating game controller");
 
-            // the path is just everything after the namespace
-            let path = path.split(format!("/{}/", namespace).as_str()).collect::<Vec<_>>()[1];
-            rl.set_path(path.to_string());
+    let mut game = Game::initiate();
 
-            //load the texture
-            let texture = texture_creator.load_texture(format!("./assets/{}/{}", namespace, path).as_str());
+    // register extra values to the game
+    game.sf = scale_factor;
+    let menu = MainMenuScreen::create(&mut game);
+    game.screens.push(menu);
 
-            // insert the hashmap
-            textures.insert(rl.clone().to_string(), texture.unwrap());
+    // append the assets to game instance
+    game.assets = assets;
+    game.dims = dims;
+    game.stats = stats::Stats::load();
+    game.settings = settings;
 
-            info!("Loaded texture : {}", rl.to_string());
-            counter+=1;
-        }
+    // match options from the command line
+    game.seed = args.seed;
+    if let Some(seed) = game.seed {
+        info!("Using seed {}", seed);
     }
-    info!("{} textures loaded!", counter);
-
-    counter = 0;
-
-    info!("Loading sounds...");
-    // create hashmap
-    let mut sounds : HashMap<String, Sound> = HashMap::new();
-
-    // iterate through the assets directory
-    for dir in WalkDir::new("./assets/") {
-        let mut path = String::from(dir.unwrap().path().to_str().unwrap()).replace("\\", "/");
-        // if the file is a sound, save it
-        if path.clone().to_lowercase().ends_with(".ogg") {
-            // create the resource location
-            let mut rl = ResourceLocation::empty();
-
-            // split the path by \s
-            let split : Vec<_> = path.split("/").collect();
-
-            // name space is in ./assets/>>namespace<<, so it is the third element in the list
-            let namespace = &split[2];
-            rl.set_namespace(namespace.to_string());
-
-            // the path is just everything after the namespace
-            let path = path.split(format!("/{}/", namespace).as_str()).collect::<Vec<_>>()[1];
-            rl.set_path(path.to_string());
-
-            //load the sound
-            let sound = Sound {
-                path : format!("assets/{}/{}", namespace, path),
-                resource_location: rl.clone(),
-            };
-
-            // insert the hashmap
-            sounds.insert(rl.clone().to_string(), sound);
-
-
-            info!("Loaded sound : {}", rl.to_string());
-            counter+=1;
+    if let Some(deck) = &args.deck {
+        if game.assets.decks.find(deck).is_some() {
+            game.deck = Some(deck.clone());
         }
-    }
-    info!("{} sounds loaded!", counter);
-
-    counter = 0;
-    // entirely data driven tile system
-
-    // initialise tiles
-    info!("Loading tiles...");
-
-    //create hashmap
-    let mut tiles: HashMap<String, Tile> = HashMap::new();
-    // get the immediate subdirectories for the name spaces
-    let namespaces = fs::read_dir("./data").unwrap();
-
-    // iterate through the namespaces
-    for namepath in namespaces {
-
-        // get the actual namespace
-        let mut namespace = String::from(namepath.unwrap().path().to_str().unwrap()).replace("\\", "/");
-        //                                            .\data\>>namespace<<
-        namespace = namespace.split("/").collect::<Vec<_>>()[2].to_string();
-
-        if !namespace.clone().contains(".") {
-
-            for dir in WalkDir::new(format!("./data/{}/tiles/", namespace.clone())) {
-                let path = String::from(dir.unwrap().path().to_str().unwrap()).replace("\\", "/");
-                // if the file is tile data, continue
-                if path.clone().to_lowercase().ends_with(".json") {
-                    // get the json file as a string
-                    let json = fs::read_to_string(path.clone()).unwrap();
-
-                    // read the values from the json file
-
-                    if gjson::get(json.as_str(), "type").to_string() != "tile" {
-                        continue;
-                    };
-
-                    // "name" : string
-                    let name = gjson::get(json.as_str(), "name");
-
-                    // the ResourceLocation of this JSON file
-                    let resource_location = ResourceLocation::new(
-                        &*namespace.clone(),
-                        path.split(format!("/{}/", namespace).as_str()).collect::<Vec<_>>()[1]);
-
-                    // "resource_location" : string
-                    let texture = ResourceLocation::parse(
-                        gjson::get(json.as_str(), "resource_location")
-                        .to_string());
-
-                    // "uv" {"x" : int,  "y" : int}
-                    let uv : (u32, u32) = (
-                        gjson::get(json.as_str(), "uv.x").to_string().parse::<u32>().unwrap(),
-                        gjson::get(json.as_str(), "uv.y").to_string().parse::<u32>().unwrap()
-                    );
-
-                    // "type" : string    - might remove this bit as it may not be needed
-                    let ttype = TileType::parse(gjson::get(json.as_str(), "type").to_string());
-
-                    // "size" : string
-                    let size = TileSize::parse(gjson::get(json.as_str(), "size").to_string().as_str());
-
-                    // "origin" {"x" : int ,  "y" :  int }  - at the moment it refers to where the centre point of the sprite is,
-                    //                                  but will be changed to be the centre point of the hitbox, as the sprites
-                    //                                  should all have the origin of (0,0) to render correctly
-                    let origin : (i32, i32) = (
-                        gjson::get(json.as_str(), "origin.x").to_string().parse::<i32>().unwrap(),
-                        gjson::get(json.as_str(), "origin.y").to_string().parse::<i32>().unwrap()
-                    );
-
-                    // "collision" : bool
-                    let collision : bool = gjson::get(json.as_str(), "collision").to_string().parse::<bool>().unwrap();
-                    let mut collison_box : Option<(u32,u32)>;
-
-                    // "collision_box" {"x" : int ,  "y" :  int }
-                    if collision {
-                        collison_box = Some((
-                            gjson::get(json.as_str(), "collision_box.x").to_string().parse::<u32>().unwrap(),
-                            gjson::get(json.as_str(), "collision_box.y").to_string().parse::<u32>().unwrap()
-                        ));
-                    }
-                    else {
-                        collison_box = None
-                    }
-
-                    // create the tile and append it to the hashmap
-                    let tile = Tile::create(name.to_string(), resource_location.clone(), texture, uv, ttype, size, origin, collision, collison_box);
-                    tiles.insert(resource_location.to_string(), tile);
-
-                    info!("Loaded tile : {}", resource_location.to_string());
-
-                    counter += 1;
-                }
-
-            }
+        else {
+            log::warn!("There is no deck called {}", deck);
         }
     }
+    if let Some(players) = args.players {
+        game.player_count = players;
+    }
 
-    info!("{} tiles loaded!", counter);
-
-    counter = 0;
-    // entirely data driven card system
-
-    // initialise cards
-    info!("Loading cards...");
-
-    //create hashmap
-    let mut cards: HashMap<String, Card> = HashMap::new();
-    // get the immediate subdirectories for the name spaces
-    let namespaces = fs::read_dir("./data").unwrap();
-
-    // iterate through the namespaces
-    for namepath in namespaces {
-
-        // get the actual namespace
-        let mut namespace = String::from(namepath.unwrap().path().to_str().unwrap()).replace("\\", "/");
-        //                                            .\data\>>namespace<<
-        namespace = namespace.split("/").collect::<Vec<_>>()[2].to_string();
-
-        if !namespace.clone().contains(".") {
-
-            for dir in WalkDir::new(format!("./data/{}/cards/", namespace.clone())) {
-                let path = String::from(dir.unwrap().path().to_str().unwrap()).replace("\\", "/");
-                // if the file is tile data, continue
-                if path.clone().to_lowercase().ends_with(".json") {
-                    // get the json file as a string
-                    let json = fs::read_to_string(path.clone()).unwrap();
-
-                    // read the values from the json file
-
-                    if gjson::get(json.as_str(), "type").to_string() != "card" {
-                        continue;
-                    };
-
-                    // "name" : string
-                    let name = gjson::get(json.as_str(), "name");
-
-                    // the ResourceLocation of this JSON file
-                    let resource_location = ResourceLocation::new(
-                        &*namespace.clone(),
-                        path.split(format!("/{}/", namespace).as_str()).collect::<Vec<_>>()[1]);
-
-                    // "resource_location" : string
-                    let texture = ResourceLocation::parse(
-                        gjson::get(json.as_str(), "resource_location")
-                            .to_string());
-
-                    // create the card and append it to the hashmap
-                    let card = Card::create(name.to_string(), resource_location.clone(), texture.clone());
-                    cards.insert(resource_location.to_string(), card);
-
-                    info!("Loaded card : {}", resource_location.to_string());
-
-                    counter += 1;
-                }
-
-            }
+    // --replay <file> skips the menus and plays back a recorded match
+    if let Some(path) = &args.replay {
+        if let Some(replay) = replay::Replay::load(path) {
+            game.start_replay(replay);
         }
     }
 
-    info!("{} cards loaded!", counter);
+    // let the player know if anything couldn't be loaded
+    game.show_load_errors(load_errors);
 
-
-    // register event pump to handle inputs
-    let mut event_pump = sdl_ctx.event_pump().unwrap();
-
-    canvas.present();
-
-    info!("Initiating game controller");
-
-    let mut game = Game::initiate();
-
-    // register extra values to the game
-    game.sf = scale_factor;
-    game.current_screen = Some(MainMenuScreen::create(&mut game));
-
-    // append hashmaps to game instance
-    game.tiles = tiles;
-    game.sounds = sounds;
-    game.dims = dims;
-    game.cards = cards;
+    // --dev loads files again as they are saved
+    let mut hot_reloader = if args.dev {Some(HotReloader::create(&args.assets_dir, &args.data_dir, &game.settings.packs))} else {None};
 
     /// Delta refers to the time taken between showing two frames. This value is often used for physics related operations, as this allows the simulation to not be affected by the frame rate of the computer.
     /// You can see that happening in [`Entity::physics`]
@@ -392,12 +257,41 @@ fn main() {
 
             game.use_finger = false;
 
+            // load everything again when the resource packs are changed
+            if game.reload_assets {
+                game.reload_assets = false;
+                info!("Reloading assets");
+                let mut load_errors = vec![];
+                let packs = ResourcePack::stack(&args.assets_dir, &args.data_dir, &game.settings.packs, &mut load_errors);
+                game.assets = Assets::load(&packs, &mut load_errors);
+                textures = loader::load_textures(&packs, &texture_creator, &mut load_errors);
+                game.show_load_errors(load_errors);
+                if let Some(hot_reloader) = &mut hot_reloader {
+                    *hot_reloader = HotReloader::create(&args.assets_dir, &args.data_dir, &game.settings.packs);
+                }
+            }
+            if let Some(hot_reloader) = &mut hot_reloader {
+                hot_reloader.update(&mut game.assets, &mut textures, &texture_creator);
+            }
+
+            // switch between fullscreen and a window if the settings changed
+            if displayed != (game.settings.fullscreen, game.settings.scale) {
+                displayed = (game.settings.fullscreen, game.settings.scale);
+                apply_display(canvas, displayed, (display.w as u32, display.h as u32));
+                scale_factor = fit_viewport(canvas, displayed.1);
+                game.sf = scale_factor;
+            }
+
             // draw background texture
+            let offset = render::get_viewport().lock().unwrap().get_offset();
+            canvas.set_draw_color(Color::RGB(0, 0, 0));
+            canvas.clear();
             canvas
                 .copy_ex(
-                    &textures.get("memory_game:background.png").unwrap(),
+                    textures.get(&ResourceLocation::new("memory_game", "background.png"))
+                        .or(textures.get(&ResourceLocation::new("game", "missing.png"))).unwrap(),
                     None,
-                    Rect::new(0, 0, dims.0, dims.1),
+                    Rect::new(offset.0, offset.1, dims.0, dims.1),
                     0.0,
                     None,
                     false,
@@ -414,16 +308,23 @@ fn main() {
             // get keys that are pressed
             game.events = vec![];
             for event in event_pump.poll_iter() {
+                // refit the game when the window changes size
+                if let Event::Window { win_event: WindowEvent::SizeChanged(..), .. } = event {
+                    scale_factor = fit_viewport(canvas, game.settings.scale);
+                    game.sf = scale_factor;
+                }
                 game.events.push(event.clone());
             }
 
-            game.mouse = ((event_pump.mouse_state().x() / scale_factor) as u32, (event_pump.mouse_state().y() / scale_factor) as u32);
+            // the mouse position in game pixels
+            let mouse = render::get_viewport().lock().unwrap().to_game((event_pump.mouse_state().x(), event_pump.mouse_state().y()));
+            game.mouse = mouse;
 
             // run a game cycle
-            game.cycle(delta, (event_pump.mouse_state().x() / scale_factor) as u32, (event_pump.mouse_state().y() / scale_factor) as u32, dims);
+            game.cycle(delta, mouse.0, mouse.1, dims);
 
             // run game render
-            game.render(canvas, scale_factor, &textures, dims, (event_pump.mouse_state().x() / scale_factor) as u32, (event_pump.mouse_state().y() / scale_factor) as u32);
+            game.render(canvas, scale_factor, &textures, dims, mouse.0, mouse.1);
 
             // present screen buffer to user
             canvas.present();
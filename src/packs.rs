@@ -0,0 +1,209 @@
+use std::fs;
+use std::path::PathBuf;
+use log::info;
+use walkdir::WalkDir;
+use crate::archive::ZipArchive;
+use crate::loader;
+use crate::loader::LoadError;
+use crate::resource_location::ResourceLocation;
+use crate::utils;
+
+// where a pack's files come from
+enum Contents {
+    Folder { assets : PathBuf, data : PathBuf },
+    Zip { archive : ZipArchive, prefix : String } // the prefix is the folder the pack is in inside the archive, if it has one
+}
+
+/// A file provided by a pack, along with the resource location it is loaded as
+pub struct PackFile {
+    pub resource_location : ResourceLocation,
+    pub path : String, // where the file is, for the logs
+    entry : String // the path to read it from, on disk or inside the archive
+}
+
+/// A set of textures, sounds and data files. Packs are stacked on top of the game's own files, and a file in a later pack
+/// replaces the one at the same [`ResourceLocation`] in the packs before it
+pub struct ResourcePack {
+    name : String,
+    contents : Contents
+}
+
+impl ResourcePack {
+
+    /// The game's own files, from the assets and data directories. They are always loaded first so any pack can replace them
+    pub fn built_in(assets_dir : &str, data_dir : &str) -> Self {
+        Self {
+            name: String::from("built in"),
+            contents: Contents::Folder { assets: PathBuf::from(assets_dir), data: PathBuf::from(data_dir) }
+        }
+    }
+
+    /// Where resource packs are kept, in the user data folder. Each pack is a folder or a zip archive holding `assets/<namespace>/...` and `data/<namespace>/...`
+    pub fn dir() -> PathBuf {
+        utils::user_data_dir().join("resourcepacks")
+    }
+
+    /// Open the pack with this name from the packs directory
+    pub fn open(name : &str) -> Result<Self, LoadError> {
+        let path = Self::dir().join(name);
+        if path.is_dir() {
+            return Ok(Self { name: name.to_string(), contents: Contents::Folder { assets: path.join("assets"), data: path.join("data") } })
+        }
+        let archive = ZipArchive::open(&path).map_err(|reason| LoadError::Io { file: path.to_string_lossy().replace("\\", "/"), reason })?;
+        Ok(Self::from_archive(name, archive))
+    }
+
+    // a pack in a zip archive. Zipping a folder usually puts everything inside a folder with the same name, which is looked inside instead
+    fn from_archive(name : &str, archive : ZipArchive) -> Self {
+        let names = archive.file_names();
+        let at_root = names.iter().any(|name| name.starts_with("assets/") || name.starts_with("data/"));
+        let prefix = match names.first().and_then(|name| name.split_once("/")) {
+            Some((folder, _)) if !at_root => {format!("{}/", folder)}
+            _ => {String::new()}
+        };
+        Self { name: name.to_string(), contents: Contents::Zip { archive, prefix } }
+    }
+
+    /// The name of every pack in the packs directory, sorted
+    pub fn available() -> Vec<String> {
+        let mut names = match fs::read_dir(Self::dir()) {
+            Ok(entries) => {
+                entries.flatten()
+                    .filter(|entry| entry.path().is_dir() || entry.file_name().to_string_lossy().to_lowercase().ends_with(".zip"))
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+            }
+            Err(_) => {vec![]}
+        };
+        names.sort();
+        names
+    }
+
+    /// The built in files followed by the packs turned on in the settings, in the order they are loaded. Packs that can't be opened are left out and added to `errors`
+    pub fn stack(assets_dir : &str, data_dir : &str, enabled : &[String], errors : &mut Vec<LoadError>) -> Vec<Self> {
+        let mut packs = vec![Self::built_in(assets_dir, data_dir)];
+        for name in enabled {
+            match Self::open(name) {
+                Ok(pack) => {
+                    info!("Using resource pack {}", name);
+                    packs.push(pack)
+                }
+                Err(e) => {loader::report(errors, e)}
+            }
+        }
+        packs
+    }
+
+    /// Get the pack name
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Every file in the pack's assets with the extension, like `.png`
+    pub fn assets(&self, extension : &str) -> Vec<PackFile> {
+        self.files("assets", extension)
+    }
+
+    /// Every json file in the pack's data
+    pub fn data(&self) -> Vec<PackFile> {
+        self.files("data", ".json")
+    }
+
+    // every file under assets/ or data/ with the extension. The first folder inside is the namespace
+    fn files(&self, kind : &str, extension : &str) -> Vec<PackFile> {
+        let mut files = vec![];
+        match &self.contents {
+            Contents::Folder { assets, data } => {
+                let root = if kind == "assets" {assets} else {data};
+                if !root.is_dir() {
+                    return files
+                }
+                for dir in WalkDir::new(root).sort_by_file_name().into_iter().flatten() {
+                    let path = dir.path().to_string_lossy().replace("\\", "/");
+                    if let Some(resource_location) = location(&loader::relative_path(root, dir.path()), extension) {
+                        files.push(PackFile { resource_location, path: path.clone(), entry: path });
+                    }
+                }
+            }
+            Contents::Zip { archive, prefix } => {
+                for name in archive.file_names() {
+                    let relative = match name.strip_prefix(&format!("{}{}/", prefix, kind)) {
+                        Some(relative) => {relative}
+                        None => {continue}
+                    };
+                    if let Some(resource_location) = location(relative, extension) {
+                        files.push(PackFile { resource_location, path: format!("{}/{}", self.name, name), entry: name.clone() });
+                    }
+                }
+            }
+        }
+        files
+    }
+
+    /// Read a file from the pack
+    pub fn read(&self, file : &PackFile) -> Result<Vec<u8>, LoadError> {
+        let result = match &self.contents {
+            Contents::Folder { .. } => {fs::read(&file.entry).map_err(|e| e.to_string())}
+            Contents::Zip { archive, .. } => {archive.read(&file.entry)}
+        };
+        result.map_err(|reason| LoadError::Io { file: file.path.clone(), reason })
+    }
+}
+
+// the resource location of a file at `namespace/path`, if it has the extension. Namespaces with a dot in are skipped, like the game has always done
+fn location(relative : &str, extension : &str) -> Option<ResourceLocation> {
+    if !relative.to_lowercase().ends_with(extension) {
+        return None
+    }
+    let (namespace, path) = relative.split_once("/")?;
+    if namespace.contains(".") {
+        return None
+    }
+    Some(ResourceLocation::new(namespace, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::tests::zip;
+
+    fn locations(files : &[PackFile]) -> Vec<String> {
+        files.iter().map(|file| file.resource_location.to_string()).collect()
+    }
+
+    #[test]
+    fn zip_with_files_at_the_root() {
+        let archive = ZipArchive::parse(zip(&[
+            ("assets/test/cards/apple.png", b"png", false),
+            ("data/test/cards/apple.json", b"{}", true),
+            ("pack.txt", b"about", false)
+        ])).unwrap();
+        let pack = ResourcePack::from_archive("root.zip", archive);
+        assert_eq!(locations(&pack.assets(".png")), vec!["test:cards/apple.png"]);
+        let data = pack.data();
+        assert_eq!(locations(&data), vec!["test:cards/apple.json"]);
+        assert_eq!(pack.read(&data[0]).unwrap(), b"{}");
+    }
+
+    #[test]
+    fn zip_with_a_top_level_folder() {
+        let archive = ZipArchive::parse(zip(&[
+            ("fruit/", b"", false),
+            ("fruit/assets/test/cards/apple.png", b"png", true),
+            ("fruit/data/test/cards/apple.json", b"{}", false),
+            ("fruit/data/test/cards/pear.json", b"[]", true)
+        ])).unwrap();
+        let pack = ResourcePack::from_archive("fruit.zip", archive);
+        assert_eq!(locations(&pack.assets(".png")), vec!["test:cards/apple.png"]);
+        let data = pack.data();
+        assert_eq!(locations(&data), vec!["test:cards/apple.json", "test:cards/pear.json"]);
+        assert_eq!(data[1].path, "fruit.zip/fruit/data/test/cards/pear.json");
+        assert_eq!(pack.read(&data[1]).unwrap(), b"[]");
+    }
+
+    #[test]
+    fn files_outside_a_namespace_are_skipped() {
+        let archive = ZipArchive::parse(zip(&[("data/loose.json", b"{}", false), ("data/.hidden/a.json", b"{}", false)])).unwrap();
+        assert!(ResourcePack::from_archive("loose.zip", archive).data().is_empty());
+    }
+}
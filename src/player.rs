@@ -0,0 +1,57 @@
+use crate::ai::{AiPlayer, Difficulty};
+
+/// Who picks the cards for a player
+pub enum Controller {
+    Human,
+    Computer(AiPlayer)
+}
+
+/// Someone taking part in a match. Their score is kept by the [`MatchRules`]
+///
+/// [`MatchRules`]: crate::rules::MatchRules
+pub struct Player {
+    name : String,
+    controller : Controller
+}
+
+impl Player {
+
+    /// Create a new player instance, controlled by a person
+    pub fn create(name : String) -> Self {
+        Self {
+            name,
+            controller: Controller::Human
+        }
+    }
+
+    /// Get the player's name
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Change the player's name
+    pub fn set_name(&mut self, name : String) {
+        self.name = name
+    }
+
+    /// Hand the player over to a person, or the computer at a given difficulty
+    pub fn set_computer(&mut self, difficulty : Option<Difficulty>) {
+        self.controller = match difficulty {
+            Some(difficulty) => {Controller::Computer(AiPlayer::create(difficulty))}
+            None => {Controller::Human}
+        }
+    }
+
+    /// Whether a person picks this player's cards
+    pub fn is_human(&self) -> bool {
+        matches!(self.controller, Controller::Human)
+    }
+
+    /// The computer player, if the computer is playing
+    pub fn get_ai_mut(&mut self) -> Option<&mut AiPlayer> {
+        match &mut self.controller {
+            Controller::Computer(ai) => {Some(ai)}
+            Controller::Human => {None}
+        }
+    }
+}
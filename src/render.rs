@@ -1,5 +1,5 @@
 use once_cell::sync::OnceCell;
-use sdl2::rect::Rect;
+use sdl2::rect::{Point, Rect};
 use sdl2::render::{Texture, WindowCanvas};
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
@@ -9,11 +9,15 @@ use log::warn;
 use sdl2::keyboard::Scancode::I;
 use sdl2::libc::stat;
 use sdl2::pixels::Color;
+use crate::assets::Registry;
 use crate::render;
 use crate::resource_location::ResourceLocation;
 
 pub const TARGET_DIMENSIONS: (u32, u32) = (448, 252);
 
+/// The size of a single character in `game:gui/font.png`
+pub const FONT_SIZE: (u32, u32) = (6, 9);
+
 /// A struct which holds all relevant information for displaying an item's texture.
 #[derive(Debug)]
 pub struct AssetData {
@@ -90,6 +94,80 @@ pub fn get_actual_dimensions() -> &'static Mutex<Dimensions> {
     })
 }
 
+/// Where the game area sits in the window. The game is drawn at a whole number scale so the pixels stay square,
+/// and anything left over around it is filled with black bars
+pub struct Viewport {
+    offset : (i32, i32), // the top left of the game area, in game pixels
+    scale : i32
+}
+
+impl Viewport {
+    /// Fit the game area into a window, at the biggest whole scale that fits. `max_scale` limits the scale if set
+    pub fn fit(window : (u32, u32), max_scale : Option<u32>) -> Self {
+        let fits = (window.0 / TARGET_DIMENSIONS.0).min(window.1 / TARGET_DIMENSIONS.1).max(1);
+        let scale = match max_scale {
+            Some(max) => {fits.min(max.max(1))}
+            None => {fits}
+        } as i32;
+        Self {
+            offset: ((window.0 as i32 / scale - TARGET_DIMENSIONS.0 as i32) / 2, (window.1 as i32 / scale - TARGET_DIMENSIONS.1 as i32) / 2),
+            scale
+        }
+    }
+
+    pub fn get_offset(&self) -> (i32, i32) {
+        self.offset
+    }
+
+    pub fn get_scale(&self) -> i32 {
+        self.scale
+    }
+
+    /// Turn a position in the window into game pixels, kept inside the game area
+    pub fn to_game(&self, window : (i32, i32)) -> (u32, u32) {
+        (
+            (window.0 / self.scale - self.offset.0).clamp(0, TARGET_DIMENSIONS.0 as i32 - 1) as u32,
+            (window.1 / self.scale - self.offset.1).clamp(0, TARGET_DIMENSIONS.1 as i32 - 1) as u32
+        )
+    }
+}
+
+/// Access the current viewport, updated whenever the window changes size
+pub fn get_viewport() -> &'static Mutex<Viewport> {
+    static INSTANCE : OnceCell<Mutex<Viewport>> = OnceCell::new();
+    INSTANCE.get_or_init(|| {
+        Mutex::new(Viewport {
+            offset: (0, 0),
+            scale: 1
+        })
+    })
+}
+
+/// Fill the window outside the game area with black
+pub fn draw_bars(canvas: &mut WindowCanvas, sf: i32) {
+    let offset = get_viewport().lock().unwrap().get_offset();
+    if offset == (0, 0) {
+        return
+    }
+    let (width, height) = canvas.output_size().unwrap_or(TARGET_DIMENSIONS);
+    let size = ((width as i32 / sf) as u32 + 1, (height as i32 / sf) as u32 + 1);
+    canvas.set_scale(sf as f32, sf as f32).expect("TODO: panic message");
+    canvas.set_draw_color(Color::RGB(0, 0, 0));
+    let right = offset.0 + TARGET_DIMENSIONS.0 as i32;
+    let bottom = offset.1 + TARGET_DIMENSIONS.1 as i32;
+    let bars = [
+        Rect::new(0, 0, offset.0.max(0) as u32, size.1),
+        Rect::new(right, 0, (size.0 as i32 - right).max(0) as u32, size.1),
+        Rect::new(0, 0, size.0, offset.1.max(0) as u32),
+        Rect::new(0, bottom, size.0, (size.1 as i32 - bottom).max(0) as u32),
+    ];
+    for bar in bars {
+        if bar.width() > 0 && bar.height() > 0 {
+            let _ = canvas.fill_rect(bar);
+        }
+    }
+}
+
 /// Access a static list of mouse icons
 pub fn get_icons() -> &'static Mutex<HashMap<&'static str, AssetData>> {
     static INSTANCE: OnceCell<Mutex<HashMap<&'static str, AssetData>>> = OnceCell::new();
@@ -126,9 +204,48 @@ pub fn get_icons() -> &'static Mutex<HashMap<&'static str, AssetData>> {
 
 
 /// Draws textures to the screen pixel-perfectly
-pub fn draw_pp_texture(x: i32, y: i32, ass: &AssetData, mut canvas: &mut WindowCanvas, sf: i32, textures : &HashMap<String, Texture>) {
+pub fn draw_pp_texture(x: i32, y: i32, ass: &AssetData, canvas: &mut WindowCanvas, sf: i32, textures : &Registry<Texture>) {
+    draw_transformed_texture(&Transform::at(x, y), ass, canvas, sf, textures)
+}
+
+/// Where a texture is drawn and how it is changed on the way, for [`draw_transformed_texture`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub x : i32,
+    pub y : i32,
+    pub scale : (f32, f32), // stretched around the origin, 1 being the texture's own size
+    pub rotation : f64, // clockwise around the origin, in degrees
+    pub alpha : u8 // 0 being see through
+}
+
+impl Transform {
+
+    /// Draw at a point, unchanged
+    pub fn at(x : i32, y : i32) -> Self {
+        Self {
+            x,
+            y,
+            scale: (1.0, 1.0),
+            rotation: 0.0,
+            alpha: 255
+        }
+    }
+}
+
+/// Draws a texture stretched and turned around its origin, so a card squashed to half its width stays centred on the same spot, and faded by the alpha
+pub fn draw_transformed_texture(transform: &Transform, ass: &AssetData, mut canvas: &mut WindowCanvas, sf: i32, textures : &Registry<Texture>) {
+    let Transform { x, y, scale, rotation, alpha } = *transform;
     let uv = ass.uv.unwrap();
-    let tex_rect = Rect::new(x - ass.origin.0, y - ass.origin.1, uv.w as u32, uv.h as u32);
+    let width = (uv.w as f32 * scale.0).round() as i32;
+    let height = (uv.h as f32 * scale.1).round() as i32;
+    // nothing to draw when squashed flat, like halfway through a flip, or faded out
+    if width <= 0 || height <= 0 || alpha == 0 {
+        return
+    }
+    let origin = ((ass.origin.0 as f32 * scale.0).round() as i32, (ass.origin.1 as f32 * scale.1).round() as i32);
+    // move into the game area, in case there are bars around it
+    let offset = get_viewport().lock().unwrap().get_offset();
+    let tex_rect = Rect::new(x - origin.0 + offset.0, y - origin.1 + offset.1, width as u32, height as u32);
     let mut id = ass.resource_location.clone();
 
     canvas
@@ -136,7 +253,7 @@ pub fn draw_pp_texture(x: i32, y: i32, ass: &AssetData, mut canvas: &mut WindowC
         .expect("TODO: panic message");
 
     // get texture from the map
-    let mut texture = textures.get(&id.to_string());
+    let mut texture = textures.get(&id);
 
     // if the texture is missing, show missing texture
     if texture.is_none(){
@@ -144,20 +261,51 @@ pub fn draw_pp_texture(x: i32, y: i32, ass: &AssetData, mut canvas: &mut WindowC
             warn!("Texture at {} could not be found!", id.to_string())
         }
         get_missing_list().lock().unwrap().push(id.clone().to_string());
-        texture = textures.get(&ResourceLocation::new("game", "missing.png").to_string());
+        texture = textures.get(&ResourceLocation::new("game", "missing.png"));
     }
 
 
+    // textures are shared, so the alpha is put back once it has been drawn
+    let texture = texture.unwrap();
+    if alpha < 255 {
+        unsafe { sdl2::sys::SDL_SetTextureAlphaMod(texture.raw(), alpha) };
+    }
     canvas
-        .copy_ex(&texture.unwrap(),
+        .copy_ex(texture,
                  uv,
                  tex_rect,
-                 0.0,
-                 None,
+                 rotation,
+                 Point::new(origin.0, origin.1),
                  false,
                  false,
         )
         .expect("TODO: panic message");
+    if alpha < 255 {
+        unsafe { sdl2::sys::SDL_SetTextureAlphaMod(texture.raw(), 255) };
+    }
+}
+
+/// Draws a line of text with the pixel font, `x` and `y` being the top left of the first character. Lowercase letters are drawn as uppercase.
+pub fn draw_text(x: i32, y: i32, text: &str, canvas: &mut WindowCanvas, sf: i32, textures : &Registry<Texture>) {
+    for (i, character) in text.chars().enumerate() {
+        // the font texture holds the printable ascii characters, 16 to a row
+        let mut code = character as u32;
+        if !(32..128).contains(&code) {
+            code = '?' as u32;
+        }
+        code -= 32;
+        let ass = AssetData {
+            uv: Some(Rect::new(((code % 16) * FONT_SIZE.0) as i32, ((code / 16) * FONT_SIZE.1) as i32, FONT_SIZE.0, FONT_SIZE.1)),
+            origin: (0, 0),
+            resource_location: ResourceLocation::new("game", "gui/font.png"),
+        };
+        draw_pp_texture(x + (i as u32 * FONT_SIZE.0) as i32, y, &ass, canvas, sf, textures);
+    }
+}
+
+/// The width in pixels of a line of text drawn with [`draw_text`]
+pub fn text_width(text: &str) -> u32 {
+    text.chars().count() as u32 * FONT_SIZE.0
 }
 
 
@@ -0,0 +1,154 @@
+use std::fs;
+use std::path::PathBuf;
+use log::{info, warn};
+use crate::utils;
+
+/// A record of a match: everything needed to deal the same board, and every card flipped in order.
+/// Saved as a small json file, like the data files
+pub struct Replay {
+    pub seed : u64,
+    pub deck : Option<String>,
+    pub pairs : Option<u32>,
+    pub players : usize,
+    flips : Vec<(f32, usize)> // the match time and board index of every accepted flip
+}
+
+impl Replay {
+
+    /// Start a new, empty recording
+    pub fn create(seed : u64, deck : Option<String>, pairs : Option<u32>, players : usize) -> Self {
+        Self {
+            seed,
+            deck,
+            pairs,
+            players,
+            flips: vec![]
+        }
+    }
+
+    /// Add a flip to the recording
+    pub fn record(&mut self, time : f32, index : usize) {
+        self.flips.push((time, index))
+    }
+
+    /// Take the last flip back off the recording
+    pub fn undo(&mut self) {
+        self.flips.pop();
+    }
+
+    /// The flip at a position in the recording, if there is one
+    pub fn get_flip(&self, position : usize) -> Option<(f32, usize)> {
+        self.flips.get(position).copied()
+    }
+
+    /// Write the replay out as json
+    pub fn to_json(&self) -> String {
+        let flips = self.flips.iter().map(|(time, index)| format!("[{:.3},{}]", time, index)).collect::<Vec<_>>().join(",");
+        let deck = match &self.deck {
+            Some(deck) => {format!("\"{}\"", deck.replace('\\', "\\\\").replace('"', "\\\""))}
+            None => {String::from("null")}
+        };
+        let pairs = match self.pairs {
+            Some(pairs) => {pairs.to_string()}
+            None => {String::from("null")}
+        };
+        format!("{{\"type\":\"replay\",\"seed\":{},\"deck\":{},\"pairs\":{},\"players\":{},\"flips\":[{}]}}\n", self.seed, deck, pairs, self.players, flips)
+    }
+
+    /// Read a replay written by [`Replay::to_json`]
+    pub fn parse(json : &str) -> Option<Self> {
+        if gjson::get(json, "type").to_string() != "replay" {
+            return None
+        }
+        let deck = gjson::get(json, "deck");
+        let pairs = gjson::get(json, "pairs");
+        // a flip missing its time or index means the file is broken, so it is refused rather than replayed wrong
+        let flips = gjson::get(json, "flips").array().iter().map(|flip| {
+            let flip = flip.array();
+            Some((flip.first()?.f32(), flip.get(1)?.u32() as usize))
+        }).collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            seed: gjson::get(json, "seed").u64(),
+            deck: if deck.kind() == gjson::Kind::String {Some(deck.to_string())} else {None},
+            pairs: if pairs.kind() == gjson::Kind::Number {Some(pairs.u32())} else {None},
+            players: gjson::get(json, "players").u32().max(1) as usize,
+            flips
+        })
+    }
+
+    /// Load a replay from a file
+    pub fn load(path : &str) -> Option<Self> {
+        match fs::read_to_string(path) {
+            Ok(json) => {
+                let replay = Self::parse(&json);
+                if replay.is_none() {
+                    warn!("{} isn't a replay file", path);
+                }
+                replay
+            }
+            Err(e) => {
+                warn!("Couldn't read replay {}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    /// Where finished matches are saved
+    pub fn dir() -> PathBuf {
+        utils::user_data_dir().join("replays")
+    }
+
+    /// Save the replay in the replays folder, named after the time it was saved
+    pub fn save(&self) {
+        let path = Self::dir().join(format!("replay_{}.json", chrono::Local::now().format("%Y-%m-%d_%H-%M-%S")));
+        let result = fs::create_dir_all(Self::dir()).and_then(|_| fs::write(&path, self.to_json()));
+        match result {
+            Ok(_) => {info!("Saved replay to {}", path.display())}
+            Err(e) => {warn!("Couldn't save replay: {}", e)}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut replay = Replay::create(20240131, Some(String::from("memory_game:decks/classic.json")), Some(8), 3);
+        replay.record(0.5, 4);
+        replay.record(1.25, 11);
+        replay.record(2.0, 0);
+        replay.undo();
+        let read = Replay::parse(&replay.to_json()).unwrap();
+        assert_eq!(read.seed, 20240131);
+        assert_eq!(read.deck.as_deref(), Some("memory_game:decks/classic.json"));
+        assert_eq!(read.pairs, Some(8));
+        assert_eq!(read.players, 3);
+        assert_eq!(read.flips, vec![(0.5, 4), (1.25, 11)]);
+        assert_eq!(read.get_flip(2), None);
+    }
+
+    #[test]
+    fn round_trip_without_deck_or_pairs() {
+        let read = Replay::parse(&Replay::create(1, None, None, 1).to_json()).unwrap();
+        assert_eq!(read.deck, None);
+        assert_eq!(read.pairs, None);
+        assert!(read.flips.is_empty());
+    }
+
+    #[test]
+    fn deck_name_is_escaped() {
+        let deck = String::from("odd \"deck\" \\ name");
+        let read = Replay::parse(&Replay::create(1, Some(deck.clone()), None, 2).to_json()).unwrap();
+        assert_eq!(read.deck, Some(deck));
+    }
+
+    #[test]
+    fn broken_files_are_refused() {
+        assert!(Replay::parse("{\"type\":\"save\",\"seed\":1,\"players\":1,\"flips\":[]}").is_none());
+        assert!(Replay::parse("{\"type\":\"replay\",\"seed\":1,\"players\":1,\"flips\":[[0.5,1],[0.75]]}").is_none());
+        assert!(Replay::parse("{\"type\":\"replay\",\"seed\":1,\"players\":1,\"flips\":[[]]}").is_none());
+        assert!(Replay::parse("not json").is_none());
+    }
+}
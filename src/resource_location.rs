@@ -1,3 +1,5 @@
+use std::hash::{Hash, Hasher};
+
 #[derive(Debug)]
 /// Reference a resource from a resource hashmap, whether that is a sound, texture or tile. Can be used for other things too, not exclusively for file stuff.
 pub struct ResourceLocation {
@@ -7,7 +9,7 @@ pub struct ResourceLocation {
 
 impl PartialEq<Self> for ResourceLocation {
     fn eq(&self, other: &Self) -> bool {
-        self.path.eq(&other.path) && self.path.eq(&other.namespace)
+        self.namespace.eq(&other.namespace) && self.path.eq(&other.path)
     }
 }
 
@@ -15,6 +17,14 @@ impl Eq for ResourceLocation {
 
 }
 
+// hashed the same way it is compared, so it can key a Registry
+impl Hash for ResourceLocation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.namespace.hash(state);
+        self.path.hash(state);
+    }
+}
+
 impl ResourceLocation {
     /// Create a new instance of a resource location given a namespace and path
     pub fn new(namespace : &str, path : &str) -> Self {
@@ -47,10 +57,12 @@ impl ResourceLocation {
         format!("{}:{}", self.namespace, self.path)
     }
 
-    /// Parse a resource location from a string
-    pub fn parse(value : String) -> Self {
-        let vals = value.split(":").into_iter().collect::<Vec<&str>>();
-        Self::new(vals.get(0).unwrap(), vals.get(1).unwrap())
+    /// Parse a resource location from a string in the form `namespace:path`, if it is one
+    pub fn try_parse(value : &str) -> Option<Self> {
+        match value.split_once(":") {
+            Some((namespace, path)) if !namespace.is_empty() && !path.is_empty() => {Some(Self::new(namespace, path))}
+            _ => {None}
+        }
     }
 }
 
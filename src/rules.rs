@@ -0,0 +1,367 @@
+use log::info;
+use crate::entities::card_entity::Card;
+
+/// The state a single card on the board can be in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CardState {
+    FaceDown,
+    FaceUp,
+    Matched
+}
+
+/// Why a flip was refused by the rules
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rejection {
+    /// There is no card at the given index
+    OutOfRange,
+    /// The card is already showing its face this turn
+    AlreadyFaceUp,
+    /// The card has already been paired up
+    AlreadyMatched,
+    /// Two cards are face up and waiting on [`MatchRules::resolve`]
+    PairPending,
+    /// Every pair has been found
+    GameOver
+}
+
+/// The result of a command given to [`MatchRules`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// Nothing changed, see the [`Rejection`] for why
+    Rejected(Rejection),
+    /// The first card of a turn was turned face up
+    Revealed { index : usize },
+    /// The second card of a turn was turned face up. The pair stays face up until [`MatchRules::resolve`] is called
+    PairRevealed { first : usize, second : usize, matched : bool },
+    /// The pending pair matched, the player scores and keeps their turn
+    Matched { player : usize, first : usize, second : usize, game_over : bool },
+    /// The pending pair didn't match, the cards are turned back over and the turn passes to the next player
+    Mismatched { player : usize, first : usize, second : usize, next : usize },
+    /// There was no pair to resolve
+    NothingToResolve
+}
+
+/// The rules of a single match, with no knowledge of rendering, input or sound. [`Game`] and [`CardEntity`] drive this by flipping cards and resolving pairs,
+/// which means a match can be played (or tested, or botted) without a window.
+///
+/// [`Game`]: crate::game::Game
+/// [`CardEntity`]: crate::entities::card_entity::CardEntity
+pub struct MatchRules {
+    board : Vec<Card>,
+    states : Vec<CardState>,
+    face_up : Vec<usize>,
+    current_turn : usize,
+    scores : Vec<u32>,
+    flips : u32
+}
+
+impl MatchRules {
+
+    /// Create the rules for a board, the cards are laid out in the order given. Players take turns in order, with one player the turn never changes hands
+    pub fn new(board : Vec<Card>, players : usize) -> Self {
+        let states = vec![CardState::FaceDown; board.len()];
+        Self {
+            board,
+            states,
+            face_up: vec![],
+            current_turn: 0,
+            scores: vec![0; players.max(1)],
+            flips: 0
+        }
+    }
+
+    /// Pick a match back up where it was left. `matched` are the board indices that had already been paired up
+    pub fn restore(board : Vec<Card>, scores : Vec<u32>, matched : &[usize], current_turn : usize, flips : u32) -> Self {
+        let mut rules = Self::new(board, scores.len());
+        for index in matched {
+            if let Some(state) = rules.states.get_mut(*index) {
+                *state = CardState::Matched;
+            }
+        }
+        rules.current_turn = current_turn % rules.scores.len();
+        rules.scores = scores;
+        rules.flips = flips;
+        rules
+    }
+
+    /// Turn the card at `index` face up
+    pub fn flip(&mut self, index : usize) -> Outcome {
+        if self.is_over() {
+            return Outcome::Rejected(Rejection::GameOver)
+        }
+        if self.face_up.len() == 2 {
+            return Outcome::Rejected(Rejection::PairPending)
+        }
+        match self.states.get(index) {
+            None => {return Outcome::Rejected(Rejection::OutOfRange)}
+            Some(CardState::FaceUp) => {return Outcome::Rejected(Rejection::AlreadyFaceUp)}
+            Some(CardState::Matched) => {return Outcome::Rejected(Rejection::AlreadyMatched)}
+            Some(CardState::FaceDown) => {}
+        }
+
+        self.states[index] = CardState::FaceUp;
+        self.face_up.push(index);
+        self.flips += 1;
+
+        if self.face_up.len() == 1 {
+            Outcome::Revealed { index }
+        }
+        else {
+            let (first, second) = (self.face_up[0], self.face_up[1]);
+            info!("Player {} picked {} and {}", self.current_turn + 1, self.board[first].get_name(), self.board[second].get_name());
+            Outcome::PairRevealed { first, second, matched: self.is_pair(first, second) }
+        }
+    }
+
+    /// Settle the two face up cards, either matching them or turning them back over
+    pub fn resolve(&mut self) -> Outcome {
+        if self.face_up.len() != 2 {
+            return Outcome::NothingToResolve
+        }
+        let (first, second) = (self.face_up[0], self.face_up[1]);
+        self.face_up.clear();
+        let player = self.current_turn;
+
+        if self.is_pair(first, second) {
+            self.states[first] = CardState::Matched;
+            self.states[second] = CardState::Matched;
+            self.scores[player] += 1;
+            let game_over = self.is_over();
+            if game_over {
+                match self.winner() {
+                    Some(winner) => {info!("Player {} wins!", winner + 1)}
+                    None => {info!("It's a draw!")}
+                }
+            }
+            Outcome::Matched { player, first, second, game_over }
+        }
+        else {
+            self.states[first] = CardState::FaceDown;
+            self.states[second] = CardState::FaceDown;
+            if !self.is_solo() {
+                self.current_turn = (self.current_turn + 1) % self.scores.len();
+                info!("Player {}'s turn", self.current_turn + 1);
+            }
+            Outcome::Mismatched { player, first, second, next: self.current_turn }
+        }
+    }
+
+    /// Two cards make a pair if they come from the same card data file
+    fn is_pair(&self, first : usize, second : usize) -> bool {
+        self.board[first].get_resource_location().to_string() == self.board[second].get_resource_location().to_string()
+    }
+
+    /// Whether two cards are face up and waiting to be resolved
+    pub fn pair_pending(&self) -> bool {
+        self.face_up.len() == 2
+    }
+
+    /// The state of the card at `index`
+    pub fn get_state(&self, index : usize) -> Option<CardState> {
+        self.states.get(index).copied()
+    }
+
+    /// The card at `index`, but only if it is face up or matched - face down cards stay secret
+    pub fn peek(&self, index : usize) -> Option<&Card> {
+        match self.states.get(index) {
+            Some(CardState::FaceUp) | Some(CardState::Matched) => {self.board.get(index)}
+            _ => {None}
+        }
+    }
+
+    /// The card at `index` whatever state it is in, for saving the board. Don't use this to make decisions, use [`MatchRules::peek`]
+    pub fn get_card(&self, index : usize) -> Option<&Card> {
+        self.board.get(index)
+    }
+
+    /// The number of cards on the board
+    pub fn card_count(&self) -> usize {
+        self.board.len()
+    }
+
+    /// The number of pairs on the board
+    pub fn pair_count(&self) -> u32 {
+        (self.board.len() / 2) as u32
+    }
+
+    /// The number of pairs found so far
+    pub fn matched_pairs(&self) -> u32 {
+        self.scores.iter().sum()
+    }
+
+    /// Whether every pair has been found
+    pub fn is_over(&self) -> bool {
+        self.matched_pairs() == self.pair_count()
+    }
+
+    /// Whether there is only one player
+    pub fn is_solo(&self) -> bool {
+        self.scores.len() == 1
+    }
+
+    /// The players with the most pairs
+    pub fn leaders(&self) -> Vec<usize> {
+        let best = self.scores.iter().max().copied().unwrap_or(0);
+        (0..self.scores.len()).filter(|p| self.scores[*p] == best).collect()
+    }
+
+    /// The player with the most pairs, or `None` on a draw
+    pub fn winner(&self) -> Option<usize> {
+        let leaders = self.leaders();
+        if leaders.len() == 1 {
+            Some(leaders[0])
+        }
+        else {
+            None
+        }
+    }
+
+    /// The number of players in the match
+    pub fn player_count(&self) -> usize {
+        self.scores.len()
+    }
+
+    /// The player whose turn it is, starting at 0
+    pub fn current_turn(&self) -> usize {
+        self.current_turn
+    }
+
+    /// The number of pairs a player has found
+    pub fn score(&self, player : usize) -> u32 {
+        self.scores.get(player).copied().unwrap_or(0)
+    }
+
+    /// How many cards have been flipped over this match
+    pub fn flips(&self) -> u32 {
+        self.flips
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource_location::ResourceLocation;
+
+    // a board of cards laid out in the order given, cards with the same name make a pair
+    fn board(names : &[&str]) -> Vec<Card> {
+        names.iter().map(|name| {
+            let rl = ResourceLocation::new("test", &format!("cards/{}.json", name));
+            Card::create(name.to_string(), rl.clone(), rl, vec![])
+        }).collect()
+    }
+
+    #[test]
+    fn matched_pair_scores_and_keeps_the_turn() {
+        let mut rules = MatchRules::new(board(&["a", "a", "b", "b"]), 2);
+        assert_eq!(rules.flip(0), Outcome::Revealed { index: 0 });
+        assert_eq!(rules.flip(1), Outcome::PairRevealed { first: 0, second: 1, matched: true });
+        assert!(rules.pair_pending());
+        assert_eq!(rules.resolve(), Outcome::Matched { player: 0, first: 0, second: 1, game_over: false });
+        assert_eq!(rules.get_state(0), Some(CardState::Matched));
+        assert_eq!(rules.get_state(1), Some(CardState::Matched));
+        assert_eq!(rules.score(0), 1);
+        assert_eq!(rules.current_turn(), 0);
+        assert_eq!(rules.flips(), 2);
+    }
+
+    #[test]
+    fn mismatch_turns_the_cards_back_and_passes_the_turn() {
+        let mut rules = MatchRules::new(board(&["a", "b", "a", "b"]), 2);
+        rules.flip(0);
+        assert_eq!(rules.flip(1), Outcome::PairRevealed { first: 0, second: 1, matched: false });
+        assert_eq!(rules.resolve(), Outcome::Mismatched { player: 0, first: 0, second: 1, next: 1 });
+        assert_eq!(rules.get_state(0), Some(CardState::FaceDown));
+        assert_eq!(rules.get_state(1), Some(CardState::FaceDown));
+        assert_eq!(rules.score(0), 0);
+        assert_eq!(rules.current_turn(), 1);
+    }
+
+    #[test]
+    fn solo_mismatch_keeps_the_turn() {
+        let mut rules = MatchRules::new(board(&["a", "b", "a", "b"]), 1);
+        rules.flip(0);
+        rules.flip(1);
+        assert_eq!(rules.resolve(), Outcome::Mismatched { player: 0, first: 0, second: 1, next: 0 });
+    }
+
+    #[test]
+    fn turns_go_round_every_player() {
+        for players in [3, 4] {
+            let mut rules = MatchRules::new(board(&["a", "b", "c", "a", "b", "c"]), players);
+            let mut turns = vec![];
+            for _ in 0..players + 1 {
+                rules.flip(0);
+                rules.flip(1);
+                rules.resolve();
+                turns.push(rules.current_turn());
+            }
+            let mut expected = (1..players).collect::<Vec<_>>();
+            expected.extend([0, 1]);
+            assert_eq!(turns, expected);
+        }
+    }
+
+    #[test]
+    fn game_over_and_winner() {
+        let mut rules = MatchRules::new(board(&["a", "b", "a", "b"]), 2);
+        // player 1 misses, player 2 finds both pairs
+        rules.flip(0);
+        rules.flip(1);
+        rules.resolve();
+        rules.flip(0);
+        rules.flip(2);
+        assert_eq!(rules.resolve(), Outcome::Matched { player: 1, first: 0, second: 2, game_over: false });
+        assert!(!rules.is_over());
+        rules.flip(1);
+        rules.flip(3);
+        assert_eq!(rules.resolve(), Outcome::Matched { player: 1, first: 1, second: 3, game_over: true });
+        assert!(rules.is_over());
+        assert_eq!(rules.winner(), Some(1));
+        assert_eq!(rules.flip(0), Outcome::Rejected(Rejection::GameOver));
+    }
+
+    #[test]
+    fn draw_has_no_winner() {
+        let mut rules = MatchRules::restore(board(&["a", "a", "b", "b"]), vec![1, 0], &[0, 1], 1, 2);
+        rules.flip(2);
+        rules.flip(3);
+        assert_eq!(rules.resolve(), Outcome::Matched { player: 1, first: 2, second: 3, game_over: true });
+        assert_eq!(rules.leaders(), vec![0, 1]);
+        assert_eq!(rules.winner(), None);
+    }
+
+    #[test]
+    fn same_card_twice_is_rejected() {
+        let mut rules = MatchRules::new(board(&["a", "a"]), 1);
+        rules.flip(0);
+        assert_eq!(rules.flip(0), Outcome::Rejected(Rejection::AlreadyFaceUp));
+        assert_eq!(rules.flips(), 1);
+    }
+
+    #[test]
+    fn matched_card_is_rejected() {
+        let mut rules = MatchRules::new(board(&["a", "a", "b", "b"]), 1);
+        rules.flip(0);
+        rules.flip(1);
+        rules.resolve();
+        assert_eq!(rules.flip(1), Outcome::Rejected(Rejection::AlreadyMatched));
+    }
+
+    #[test]
+    fn third_flip_is_rejected_until_resolved() {
+        let mut rules = MatchRules::new(board(&["a", "b", "a", "b"]), 1);
+        rules.flip(0);
+        rules.flip(1);
+        assert_eq!(rules.flip(2), Outcome::Rejected(Rejection::PairPending));
+        assert_eq!(rules.get_state(2), Some(CardState::FaceDown));
+        rules.resolve();
+        assert_eq!(rules.flip(2), Outcome::Revealed { index: 2 });
+    }
+
+    #[test]
+    fn out_of_range_and_empty_resolve() {
+        let mut rules = MatchRules::new(board(&["a", "a"]), 1);
+        assert_eq!(rules.flip(2), Outcome::Rejected(Rejection::OutOfRange));
+        assert_eq!(rules.resolve(), Outcome::NothingToResolve);
+    }
+}
@@ -0,0 +1,249 @@
+use crate::assets::Registry;
+use std::fs;
+use std::path::PathBuf;
+use log::{info, warn};
+use crate::ai::Difficulty;
+use crate::entities::card_entity::Card;
+use crate::replay::Replay;
+use crate::rules::{CardState, MatchRules};
+use crate::utils;
+
+/// A match that was quit part way through, saved as json in the user data folder so it can be continued from the main menu
+pub struct SavedMatch {
+    pub deck : Option<String>,
+    pub pairs : Option<u32>,
+    pub computer : Option<Difficulty>,
+    pub names : Vec<String>,
+    pub time : f32,
+    board : Vec<String>, // the resource location of the card in every slot, in board order
+    matched : Vec<usize>, // the board indices that have been paired up
+    scores : Vec<u32>,
+    turn : usize,
+    flips : u32,
+    pub replay : Replay // the flips so far, so the finished match still has a full replay
+}
+
+impl SavedMatch {
+
+    /// Where the match is saved
+    pub fn path() -> PathBuf {
+        utils::user_data_dir().join("save.json")
+    }
+
+    /// Whether there is a match to continue
+    pub fn exists() -> bool {
+        Self::path().exists()
+    }
+
+    /// Remove the saved match, once it has been finished or replaced by a new one
+    pub fn delete() {
+        if Self::exists() {
+            if let Err(e) = fs::remove_file(Self::path()) {
+                warn!("Couldn't remove the saved match: {}", e);
+            }
+        }
+    }
+
+    /// Take a snapshot of a match. Any card left face up is turned back over
+    pub fn create(rules : &MatchRules, deck : Option<String>, pairs : Option<u32>, computer : Option<Difficulty>, names : Vec<String>, time : f32, replay : Replay) -> Self {
+        let mut board = vec![];
+        let mut matched = vec![];
+        for index in 0..rules.card_count() {
+            if rules.get_state(index) == Some(CardState::Matched) {
+                matched.push(index);
+            }
+            board.push(rules.get_card(index).map(|card| card.get_resource_location().to_string()).unwrap_or_default());
+        }
+        Self {
+            deck,
+            pairs,
+            computer,
+            names,
+            time,
+            board,
+            matched,
+            scores: (0..rules.player_count()).map(|p| rules.score(p)).collect(),
+            turn: rules.current_turn(),
+            flips: rules.flips(),
+            replay
+        }
+    }
+
+    /// The number of players in the saved match
+    pub fn players(&self) -> usize {
+        self.scores.len()
+    }
+
+    /// The seed the saved match was dealt with
+    pub fn seed(&self) -> u64 {
+        self.replay.seed
+    }
+
+    /// Look the cards back up, in board order. Fails if any of them no longer exist
+    pub fn board(&self, cards : &Registry<Card>) -> Option<Vec<Card>> {
+        let mut board = vec![];
+        for rl in &self.board {
+            match cards.find(rl) {
+                Some(card) => {board.push(card.clone())}
+                None => {
+                    warn!("The saved match uses card {} which doesn't exist any more", rl);
+                    return None
+                }
+            }
+        }
+        Some(board)
+    }
+
+    /// Set the rules back up as they were when the match was saved
+    pub fn rules(&self, board : Vec<Card>) -> MatchRules {
+        MatchRules::restore(board, self.scores.clone(), &self.matched, self.turn, self.flips)
+    }
+
+    /// Write the match out as json
+    pub fn to_json(&self) -> String {
+        let list = |items : Vec<String>| items.join(",");
+        let quote = |text : &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+        format!("{{\"type\":\"save\",\"deck\":{},\"pairs\":{},\"computer\":{},\"names\":[{}],\"time\":{:.3},\"board\":[{}],\"matched\":[{}],\"scores\":[{}],\"turn\":{},\"flips\":{},\"replay\":{}}}\n",
+            self.deck.as_ref().map_or(String::from("null"), |deck| quote(deck)),
+            self.pairs.map_or(String::from("null"), |pairs| pairs.to_string()),
+            self.computer.map_or(String::from("null"), |difficulty| quote(difficulty.get_name())),
+            list(self.names.iter().map(|name| quote(name)).collect()),
+            self.time,
+            list(self.board.iter().map(|rl| quote(rl)).collect()),
+            list(self.matched.iter().map(|index| index.to_string()).collect()),
+            list(self.scores.iter().map(|score| score.to_string()).collect()),
+            self.turn,
+            self.flips,
+            self.replay.to_json().trim_end()
+        )
+    }
+
+    /// Read a match written by [`SavedMatch::to_json`]
+    pub fn parse(json : &str) -> Option<Self> {
+        if gjson::get(json, "type").to_string() != "save" {
+            return None
+        }
+        let deck = gjson::get(json, "deck");
+        let pairs = gjson::get(json, "pairs");
+        let computer = gjson::get(json, "computer");
+        let replay = gjson::get(json, "replay");
+        let scores = gjson::get(json, "scores").array().iter().map(|score| score.u32()).collect::<Vec<_>>();
+        if scores.is_empty() {
+            return None
+        }
+        Some(Self {
+            deck: if deck.kind() == gjson::Kind::String {Some(deck.to_string())} else {None},
+            pairs: if pairs.kind() == gjson::Kind::Number {Some(pairs.u32())} else {None},
+            computer: Difficulty::parse(&computer.to_string()),
+            names: gjson::get(json, "names").array().iter().map(|name| name.to_string()).collect(),
+            time: gjson::get(json, "time").f32(),
+            board: gjson::get(json, "board").array().iter().map(|rl| rl.to_string()).collect(),
+            matched: gjson::get(json, "matched").array().iter().map(|index| index.u32() as usize).collect(),
+            turn: gjson::get(json, "turn").u32() as usize % scores.len(),
+            scores,
+            flips: gjson::get(json, "flips").u32(),
+            replay: Replay::parse(replay.json())?
+        })
+    }
+
+    /// Load the saved match, if there is one
+    pub fn load() -> Option<Self> {
+        match fs::read_to_string(Self::path()) {
+            Ok(json) => {
+                let saved = Self::parse(&json);
+                if saved.is_none() {
+                    warn!("The saved match couldn't be read");
+                }
+                saved
+            }
+            Err(_) => {None}
+        }
+    }
+
+    /// Save the match to the user data folder
+    pub fn save(&self) {
+        let path = Self::path();
+        let result = match path.parent() {
+            Some(dir) => {fs::create_dir_all(dir).and_then(|_| fs::write(&path, self.to_json()))}
+            None => {fs::write(&path, self.to_json())}
+        };
+        match result {
+            Ok(_) => {info!("Saved the match to {}", path.display())}
+            Err(e) => {warn!("Couldn't save the match to {}: {}", path.display(), e)}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource_location::ResourceLocation;
+
+    fn cards() -> Registry<Card> {
+        let mut cards = Registry::new();
+        for name in ["a", "b", "c"] {
+            let rl = ResourceLocation::new("test", &format!("cards/{}.json", name));
+            cards.insert(rl.clone(), Card::create(name.to_string(), rl.clone(), rl, vec![]));
+        }
+        cards
+    }
+
+    fn board(cards : &Registry<Card>, names : &[&str]) -> Vec<Card> {
+        names.iter().map(|name| cards.find(&format!("test:cards/{}.json", name)).unwrap().clone()).collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        let cards = cards();
+        let mut rules = MatchRules::new(board(&cards, &["a", "b", "c", "a", "b", "c"]), 2);
+        let mut replay = Replay::create(42, Some(String::from("test:decks/\"quoted\".json")), Some(3), 2);
+        // player 1 misses, player 2 finds the a pair, then leaves a card face up
+        for (time, index) in [(0.5, 0), (1.0, 1), (2.0, 0), (2.5, 3), (3.0, 2)] {
+            rules.flip(index);
+            rules.resolve();
+            replay.record(time, index);
+        }
+        let saved = SavedMatch::create(&rules, replay.deck.clone(), replay.pairs, Some(Difficulty::Hard), vec![String::from("Ann"), String::from("Bo \"B\"")], 3.5, replay);
+
+        let read = SavedMatch::parse(&saved.to_json()).unwrap();
+        assert_eq!(read.deck.as_deref(), Some("test:decks/\"quoted\".json"));
+        assert_eq!(read.pairs, Some(3));
+        assert_eq!(read.computer, Some(Difficulty::Hard));
+        assert_eq!(read.names, vec![String::from("Ann"), String::from("Bo \"B\"")]);
+        assert_eq!(read.time, 3.5);
+        assert_eq!(read.players(), 2);
+        assert_eq!(read.seed(), 42);
+        assert_eq!(read.replay.get_flip(4), Some((3.0, 2)));
+
+        let restored = read.rules(read.board(&cards).unwrap());
+        for index in 0..6 {
+            assert_eq!(restored.get_card(index).map(|card| card.get_name()), rules.get_card(index).map(|card| card.get_name()));
+        }
+        assert_eq!(restored.get_state(0), Some(CardState::Matched));
+        assert_eq!(restored.get_state(3), Some(CardState::Matched));
+        // the card left face up is turned back over
+        assert_eq!(restored.get_state(2), Some(CardState::FaceDown));
+        assert_eq!(restored.score(0), 0);
+        assert_eq!(restored.score(1), 1);
+        assert_eq!(restored.current_turn(), 1);
+        assert_eq!(restored.flips(), rules.flips());
+    }
+
+    #[test]
+    fn missing_cards_are_refused() {
+        let cards = cards();
+        let rules = MatchRules::new(board(&cards, &["a", "a"]), 1);
+        let saved = SavedMatch::create(&rules, None, None, None, vec![], 0.0, Replay::create(1, None, None, 1));
+        let read = SavedMatch::parse(&saved.to_json()).unwrap();
+        assert_eq!(read.deck, None);
+        assert_eq!(read.computer, None);
+        assert!(read.board(&Registry::new()).is_none());
+    }
+
+    #[test]
+    fn broken_files_are_refused() {
+        assert!(SavedMatch::parse("{\"type\":\"replay\"}").is_none());
+        assert!(SavedMatch::parse("{\"type\":\"save\",\"scores\":[],\"replay\":{\"type\":\"replay\",\"flips\":[]}}").is_none());
+        assert!(SavedMatch::parse("{\"type\":\"save\",\"scores\":[0],\"replay\":null}").is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use crate::assets::Registry;
 use sdl2::event::Event;
 use sdl2::render::{Texture, WindowCanvas};
 use crate::game::Game;
@@ -20,6 +20,16 @@ pub trait Screen {
         self.get_widgets().get_mut(y).unwrap().insert(x, widget);
     }
 
+    /// Whether the screen is drawn over the one below it in the stack, like the pause menu, rather than replacing it
+    fn is_overlay(&self) -> bool {
+        false
+    }
+
+    /// Whether Escape pauses and resumes the match while this screen is on top, like the HUD and the pause menu
+    fn toggles_pause(&self) -> bool {
+        false
+    }
+
     /// Get the game instance holding the screen
     #[must_use]
     fn get_game(&mut self) -> *mut Game;
@@ -36,6 +46,13 @@ pub trait Screen {
     /// What the screen does every frame
     fn cycle(&mut self, mousex : u32, mousey : u32, events: Vec<Event>) {
         let game = unsafe { &mut *self.get_game() };
+        for widgets in self.get_widgets() {
+            for w in widgets {
+                for event in &events {
+                    w.on_event(event);
+                }
+            }
+        }
         for widgets in self.get_widgets() {
             for w in widgets {
                 let _ = w.set_selected(false);
@@ -57,8 +74,20 @@ pub trait Screen {
         }
     }
 
+    /// Called once a frame after input has been handled, for screens that need to react to their widgets changing
+    fn update(&mut self) {}
+
+    /// Move the widgets' animations on by the time since the last frame
+    fn tick(&mut self, delta : f32) {
+        for widgets in self.get_widgets() {
+            for w in widgets {
+                w.tick(delta)
+            }
+        }
+    }
+
     /// Render the screen to the ... Screen - the actual real one the player sees
-    fn render(&mut self, textures : &HashMap<String, Texture>, sf : i32, canvas : &mut WindowCanvas, dims : (u32, u32), debug : bool) {
+    fn render(&mut self, textures : &Registry<Texture>, sf : i32, canvas : &mut WindowCanvas, dims : (u32, u32), debug : bool) {
         for widgets in self.get_widgets() {
             for w in widgets {
                 w.render(textures, sf, canvas, debug);
@@ -68,4 +97,14 @@ pub trait Screen {
 
 }
 
-
+/// A change to the screen stack on [`Game`]. These are applied at the end of the frame, so a widget can't remove the screen it is on while it is still running
+pub enum ScreenCommand {
+    /// Put a screen on top of the stack
+    Push(Box<dyn Screen>),
+    /// Take the top screen off the stack
+    Pop,
+    /// Swap the top screen for another
+    Replace(Box<dyn Screen>),
+    /// Clear the stack, leaving only the given screen
+    Reset(Box<dyn Screen>)
+}
@@ -1,4 +1,10 @@
 pub(crate) mod main_menu_screen;
 pub(crate) mod hud_screen;
+pub(crate) mod deck_select_screen;
+pub(crate) mod stats_screen;
+pub(crate) mod pause_screen;
+pub(crate) mod settings_screen;
+pub(crate) mod load_error_screen;
+pub(crate) mod packs_screen;
 
 use crate::screen::Screen;
\ No newline at end of file
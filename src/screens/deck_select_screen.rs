@@ -0,0 +1,54 @@
+use crate::game::Game;
+use crate::render;
+use crate::screen::Screen;
+use crate::widget::{Alignment, Widget};
+use crate::widgets::board_size_widget::BoardSizeWidget;
+use crate::widgets::deck_widget::DeckWidget;
+use crate::widgets::label_widget::LabelWidget;
+use crate::widgets::link_widget::{Link, LinkWidget};
+use crate::widgets::play_widget::{PlayTarget, PlayWidget};
+use crate::widgets::opponent_widget::OpponentWidget;
+use crate::widgets::players_widget::PlayersWidget;
+
+/// Shown between the main menu and a match, to pick the deck, board size and who is playing
+pub struct DeckSelectScreen {
+    widgets : Vec<Vec<Box<dyn Widget>>>,
+    game : *mut Game,
+}
+
+impl Screen for DeckSelectScreen {
+    fn get_widgets(&mut self) -> &mut Vec<Vec<Box<dyn Widget>>> {
+        &mut self.widgets
+    }
+
+    fn get_game(&mut self) -> *mut Game {
+        self.game
+    }
+
+    fn set_game(&mut self, game: *mut Game) {
+        self.game = game;
+    }
+
+    fn create(game : &mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let mut ret = Self{
+            widgets: vec![],
+            game,
+        };
+        let title = "CHOOSE A DECK";
+        ret.add_widget(LabelWidget::create(Alignment::CENTRE, -(render::text_width(title) as i32) / 2, 110, title, game), 0, 0);
+
+        // click the preview to cycle the deck, the size to cycle how many pairs are dealt, the players to switch to solo and the opponent to play the computer
+        ret.add_widget(DeckWidget::create(Alignment::CENTRE, -88, 90, game), 0, 1);
+        ret.add_widget(BoardSizeWidget::create(Alignment::CENTRE, -51, -10, game), 0, 2);
+        ret.add_widget(PlayersWidget::create(Alignment::CENTRE, -39, -30, game), 0, 3);
+        ret.add_widget(OpponentWidget::create(Alignment::CENTRE, -51, -50, game), 0, 4);
+
+        ret.add_widget(PlayWidget::create(Alignment::RIGHT, -60, -80, PlayTarget::Match, game), 0, 5);
+        ret.add_widget(LinkWidget::create(Alignment::LEFT, 20, -110, "< BACK", Link::Back, game), 0, 6);
+        Box::new(ret)
+    }
+
+}
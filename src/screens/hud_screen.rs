@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 use sdl2::event::Event;
 use sdl2::render::{Texture, WindowCanvas};
-use crate::game::{Game, Turn};
+use crate::game::Game;
+use crate::render;
 use crate::screen::Screen;
 use crate::widget::{Alignment, Widget};
 use crate::widgets::crown_widget::CrownWidget;
@@ -9,7 +10,18 @@ use crate::widgets::end_screen_quit_widget::EndQuitWidget;
 use crate::widgets::play_again_widget::PlayAgainWidget;
 use crate::widgets::play_widget::PlayWidget;
 use crate::widgets::player_widget::PlayerWidget;
-use crate::widgets::score_widget::ScoreWidget;
+use crate::widgets::label_widget::LabelWidget;
+use crate::widgets::result_widget::ResultWidget;
+use crate::widgets::score_widget::{Counter, ScoreWidget};
+use crate::widgets::seed_widget::SeedWidget;
+
+// where each player's widget sits: the first two on either side at the top of the board, the next two below them
+const SEATS : [(Alignment, i32, i32); 4] = [
+    (Alignment::LEFT, 20, 80),
+    (Alignment::RIGHT, -88, 80),
+    (Alignment::LEFT, 20, -24),
+    (Alignment::RIGHT, -88, -24)
+];
 
 pub struct HudScreen {
     game : *mut Game,
@@ -29,6 +41,10 @@ impl Screen for HudScreen {
         self.game = game;
     }
 
+    fn toggles_pause(&self) -> bool {
+        true
+    }
+
     fn create(game: &mut Game) -> Box<Self>
     where
         Self: Sized
@@ -37,19 +53,43 @@ impl Screen for HudScreen {
             widgets: vec![],
             game,
         };
-        //ret.add_widget(ScoreWidget::create(Alignment::TOP, 0, 0, game), 0, 0);
+        let solo = game.rules.is_solo();
 
-        // Add the widgets to show the players current turn
-        ret.add_widget(PlayerWidget::create(Alignment::LEFT, 20, 80, Turn::Player1, game),0,0);
-        ret.add_widget(PlayerWidget::create(Alignment::RIGHT, -88, 80, Turn::Player2, game),0,0);
+        if solo {
+            // solo matches are against the clock, so show the time and flips instead of a second player
+            ret.add_widget(PlayerWidget::create(Alignment::LEFT, 20, 80, 0, game),0,0);
+            ret.add_widget(LabelWidget::create(Alignment::RIGHT, -88, 100, "TIME", game),0,0);
+            ret.add_widget(ScoreWidget::create(Alignment::RIGHT, -52, 100, Counter::Time, game),0,0);
+            ret.add_widget(LabelWidget::create(Alignment::RIGHT, -88, 86, "FLIPS", game),0,0);
+            ret.add_widget(ScoreWidget::create(Alignment::RIGHT, -52, 86, Counter::Flips, game),0,0);
+        }
+        else {
+            // Add the widgets to show the players current turn, going round the board
+            for player in 0..game.rules.player_count() {
+                let (alignment, x, y) = SEATS[player % SEATS.len()].clone();
+                ret.add_widget(PlayerWidget::create(alignment, x, y, player, game),0,0);
+            }
+        }
 
         // Add the widgets that only show once the game is over
         ret.add_widget(PlayAgainWidget::create(Alignment::LEFT, 20, -110, game),0,0);
         ret.add_widget(EndQuitWidget::create(Alignment::RIGHT, -75, -110, game),0,0);
+        ret.add_widget(SeedWidget::create(Alignment::BOTTOM, 0, 12, game),0,0);
+        if game.playback.is_some() {
+            ret.add_widget(LabelWidget::create(Alignment::TOP, -(render::text_width("REPLAY") as i32) / 2, 4, "REPLAY", game),0,0);
+        }
 
-        // Add two crowns that will display when the respective player wins
-        ret.add_widget(CrownWidget::create(Alignment::LEFT, 54, 90, game, Turn::Player1), 0,0);
-        ret.add_widget(CrownWidget::create(Alignment::RIGHT, -54, 90, game, Turn::Player2), 0,0);
+        if solo {
+            // the final time and flips show once the board is cleared
+            ret.add_widget(ResultWidget::create(Alignment::RIGHT, -88, 60, game), 0, 0);
+        }
+        else {
+            // Add a crown for each player that will display when they win
+            for player in 0..game.rules.player_count() {
+                let (alignment, x, y) = SEATS[player % SEATS.len()].clone();
+                ret.add_widget(CrownWidget::create(alignment, x + 34, y + 10, game, player), 0,0);
+            }
+        }
         Box::new(ret)
     }
 
@@ -0,0 +1,77 @@
+use crate::game::Game;
+use crate::render;
+use crate::screen::Screen;
+use crate::widget::{Alignment, Widget};
+use crate::widgets::err_widget::ErrWidget;
+use crate::widgets::label_widget::LabelWidget;
+use crate::widgets::link_widget::{Link, LinkWidget};
+
+// the height of a line of text, with a gap under it
+const LINE_HEIGHT : i32 = render::FONT_SIZE.1 as i32 + 3;
+
+// the most problems listed, so they fit on screen. The rest are in the log
+const MAX_ENTRIES : usize = 12;
+
+// the widest a line can be, in characters
+const MAX_CHARS : usize = 68;
+
+/// Shown at start up when some data or asset files couldn't be loaded, listing what was skipped. Clicking the error icon or continue goes on to the game
+pub struct LoadErrorScreen {
+    widgets : Vec<Vec<Box<dyn Widget>>>,
+    game : *mut Game,
+}
+
+impl Screen for LoadErrorScreen {
+    fn get_widgets(&mut self) -> &mut Vec<Vec<Box<dyn Widget>>> {
+        &mut self.widgets
+    }
+
+    fn get_game(&mut self) -> *mut Game {
+        self.game
+    }
+
+    fn set_game(&mut self, game: *mut Game) {
+        self.game = game;
+    }
+
+    fn create(game : &mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let mut ret = Self{
+            widgets: vec![],
+            game,
+        };
+        let title = "SOME FILES COULD NOT BE LOADED";
+        let x = -(render::text_width(title) as i32) / 2;
+        ret.add_widget(ErrWidget::create(Alignment::CENTRE, x - 26, 116, game), 0, 0);
+        ret.add_widget(LabelWidget::create(Alignment::CENTRE, x, 110, title, game), 0, 0);
+
+        let count = game.load_errors.len();
+        let summary = format!("{} {} SKIPPED, SEE THE LOG FOR DETAILS", count, if count == 1 {"FILE WAS"} else {"FILES WERE"});
+        ret.add_widget(LabelWidget::create(Alignment::CENTRE, -(render::text_width(&summary) as i32) / 2, 95, &summary, game), 0, 0);
+
+        // one line per problem, cutting the start of the path off long ones so the reason can still be read
+        let mut lines = game.load_errors.iter().take(MAX_ENTRIES).map(|error| {
+            let line = error.to_string().to_uppercase();
+            let chars = line.chars().count();
+            if chars > MAX_CHARS {
+                format!("...{}", line.chars().skip(chars + 3 - MAX_CHARS).collect::<String>())
+            }
+            else {
+                line
+            }
+        }).collect::<Vec<_>>();
+        if count > MAX_ENTRIES {
+            lines.push(format!("AND {} MORE", count - MAX_ENTRIES));
+        }
+        for (i, line) in lines.iter().enumerate() {
+            ret.add_widget(LabelWidget::create(Alignment::CENTRE, -(MAX_CHARS as i32 * render::FONT_SIZE.0 as i32) / 2, 72 - LINE_HEIGHT * i as i32, line, game), 0, 0);
+        }
+
+        let text = "CONTINUE >";
+        ret.add_widget(LinkWidget::create(Alignment::CENTRE, -(render::text_width(text) as i32) / 2, -105, text, Link::Back, game), 0, 1);
+        Box::new(ret)
+    }
+
+}
@@ -1,7 +1,9 @@
 use crate::game::Game;
+use crate::save::SavedMatch;
 use crate::screen::Screen;
 use crate::widget::{Alignment, Widget};
-use crate::widgets::play_widget::PlayWidget;
+use crate::widgets::link_widget::{Link, LinkWidget};
+use crate::widgets::play_widget::{PlayTarget, PlayWidget};
 use crate::widgets::quit_widget::QuitWidget;
 
 pub struct MainMenuScreen{
@@ -32,8 +34,14 @@ impl Screen for MainMenuScreen {
             widgets: vec![],
             game,
         };
-        ret.add_widget(PlayWidget::create(Alignment::LEFT, 60, 0, game), 0, 0);
+        ret.add_widget(PlayWidget::create(Alignment::LEFT, 60, 0, PlayTarget::DeckSelect, game), 0, 0);
         ret.add_widget(QuitWidget::create(Alignment::LEFT, 60, -40, game), 0, 0);
+        ret.add_widget(LinkWidget::create(Alignment::LEFT, 60, -75, "STATS", Link::Stats, game), 0, 0);
+        ret.add_widget(LinkWidget::create(Alignment::LEFT, 60, -90, "SETTINGS", Link::Settings, game), 0, 0);
+        // only offered when a match was left part way through
+        if SavedMatch::exists() {
+            ret.add_widget(LinkWidget::create(Alignment::LEFT, 60, 40, "CONTINUE", Link::Continue, game), 0, 0);
+        }
         Box::new(ret)
     }
 
@@ -0,0 +1,72 @@
+use crate::game::Game;
+use crate::packs::ResourcePack;
+use crate::render;
+use crate::screen::Screen;
+use crate::widget::{Alignment, Widget};
+use crate::widgets::label_widget::LabelWidget;
+use crate::widgets::link_widget::{Link, LinkWidget};
+use crate::widgets::pack_widget::{PackAction, PackWidget};
+
+// the gap between each pack in the list
+const LINE_HEIGHT : i32 = 15;
+
+// the most packs listed, so they fit on screen
+const MAX_PACKS : usize = 12;
+
+/// Lets the player turn resource packs on and off and choose which wins when two change the same file.
+/// Turned on packs are listed first, highest priority at the top, above the game's own files
+pub struct PacksScreen {
+    widgets : Vec<Vec<Box<dyn Widget>>>,
+    game : *mut Game,
+}
+
+impl Screen for PacksScreen {
+    fn get_widgets(&mut self) -> &mut Vec<Vec<Box<dyn Widget>>> {
+        &mut self.widgets
+    }
+
+    fn get_game(&mut self) -> *mut Game {
+        self.game
+    }
+
+    fn set_game(&mut self, game: *mut Game) {
+        self.game = game;
+    }
+
+    fn create(game : &mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let mut ret = Self{
+            widgets: vec![],
+            game,
+        };
+        let title = "RESOURCE PACKS";
+        ret.add_widget(LabelWidget::create(Alignment::CENTRE, -(render::text_width(title) as i32) / 2, 110, title, game), 0, 0);
+
+        // turned on packs first, highest priority at the top, then the rest of the packs directory
+        let available = ResourcePack::available();
+        let mut names = game.settings.packs.iter().rev().filter(|pack| available.contains(pack)).cloned().collect::<Vec<_>>();
+        names.extend(available.into_iter().filter(|pack| !game.settings.packs.contains(pack)));
+
+        if names.is_empty() {
+            let text = "NO PACKS FOUND IN RESOURCEPACKS";
+            ret.add_widget(LabelWidget::create(Alignment::CENTRE, -(render::text_width(text) as i32) / 2, 80, text, game), 0, 1);
+        }
+        for (row, name) in names.iter().take(MAX_PACKS).enumerate() {
+            let y = 85 - LINE_HEIGHT * row as i32;
+            ret.add_widget(PackWidget::create(Alignment::CENTRE, -110, y, name, PackAction::Toggle, game), 0, row + 1);
+            // the top pack is already the highest priority
+            if row > 0 && game.settings.packs.contains(name) {
+                ret.add_widget(PackWidget::create(Alignment::CENTRE, 95, y, name, PackAction::Raise, game), 1, row + 1);
+            }
+        }
+
+        // the game's own files are always loaded, underneath every pack
+        let rows = names.len().clamp(1, MAX_PACKS);
+        ret.add_widget(LabelWidget::create(Alignment::CENTRE, -110, 85 - LINE_HEIGHT * rows as i32, "BUILT IN", game), 0, rows + 1);
+
+        ret.add_widget(LinkWidget::create(Alignment::LEFT, 20, -110, "< BACK", Link::Back, game), 0, rows + 2);
+        Box::new(ret)
+    }
+}
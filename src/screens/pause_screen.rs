@@ -0,0 +1,79 @@
+use crate::assets::Registry;
+use sdl2::pixels::Color;
+use sdl2::render::{BlendMode, Texture, WindowCanvas};
+use crate::game::Game;
+use crate::render;
+use crate::screen::Screen;
+use crate::widget::{Alignment, Widget};
+use crate::widgets::label_widget::LabelWidget;
+use crate::widgets::link_widget::{Link, LinkWidget};
+
+/// Shown over the board when a match is paused with Escape. The match is frozen until it is resumed
+pub struct PauseScreen {
+    widgets : Vec<Vec<Box<dyn Widget>>>,
+    game : *mut Game,
+}
+
+impl PauseScreen {
+
+    // add a line of text centred on the screen
+    fn add_centred(&mut self, y : i32, text : &str, link : Option<Link>, game : &mut Game) {
+        let x = -(render::text_width(text) as i32) / 2;
+        match link {
+            Some(link) => {self.add_widget(LinkWidget::create(Alignment::CENTRE, x, y, text, link, game), 0, 0)}
+            None => {self.add_widget(LabelWidget::create(Alignment::CENTRE, x, y, text, game), 0, 0)}
+        }
+    }
+}
+
+impl Screen for PauseScreen {
+    fn get_widgets(&mut self) -> &mut Vec<Vec<Box<dyn Widget>>> {
+        &mut self.widgets
+    }
+
+    fn get_game(&mut self) -> *mut Game {
+        self.game
+    }
+
+    fn set_game(&mut self, game: *mut Game) {
+        self.game = game;
+    }
+
+    fn toggles_pause(&self) -> bool {
+        true
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+
+    fn create(game : &mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let mut ret = Self{
+            widgets: vec![],
+            game,
+        };
+        ret.add_centred(40, "PAUSED", None, game);
+        ret.add_centred(15, "RESUME", Some(Link::Resume), game);
+        ret.add_centred(0, "RESTART", Some(Link::Restart), game);
+        ret.add_centred(-15, "SETTINGS", Some(Link::Settings), game);
+        ret.add_centred(-30, "QUIT TO MENU", Some(Link::MainMenu), game);
+        Box::new(ret)
+    }
+
+    fn render(&mut self, textures : &Registry<Texture>, sf : i32, canvas : &mut WindowCanvas, _dims : (u32, u32), debug : bool) {
+        // darken the board so the menu stands out
+        canvas.set_blend_mode(BlendMode::Blend);
+        canvas.set_draw_color(Color::RGBA(0, 0, 0, 150));
+        let _ = canvas.fill_rect(None);
+        canvas.set_blend_mode(BlendMode::None);
+
+        for widgets in self.get_widgets() {
+            for w in widgets {
+                w.render(textures, sf, canvas, debug);
+            }
+        }
+    }
+}
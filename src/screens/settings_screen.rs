@@ -0,0 +1,118 @@
+use crate::game::Game;
+use crate::render;
+use crate::screen::Screen;
+use crate::settings::{DisplayMode, RevealDelay, Scale, Toggle, TransitionStyle, TransitionTime, Volume};
+use crate::widget::{Alignment, Widget};
+use crate::widgets::enum_widget::{EnumWidget, WidgetEnum};
+use crate::widgets::label_widget::LabelWidget;
+use crate::widgets::link_widget::{Link, LinkWidget};
+use crate::widgets::name_widget::NameWidget;
+use crate::widgets::players_widget::MAX_PLAYERS;
+
+// the row the option widgets are kept in, so they can be read back in order
+const OPTION_ROW : usize = 1;
+
+/// Lets the player change the settings, which are saved as soon as they change
+pub struct SettingsScreen {
+    widgets : Vec<Vec<Box<dyn Widget>>>,
+    game : *mut Game,
+}
+
+impl SettingsScreen {
+
+    // the current choice of the option widget at `indx`
+    fn option(&mut self, indx : usize) -> usize {
+        self.widgets[OPTION_ROW][indx].return_enum_int().unwrap_or(0)
+    }
+}
+
+impl Screen for SettingsScreen {
+    fn get_widgets(&mut self) -> &mut Vec<Vec<Box<dyn Widget>>> {
+        &mut self.widgets
+    }
+
+    fn get_game(&mut self) -> *mut Game {
+        self.game
+    }
+
+    fn set_game(&mut self, game: *mut Game) {
+        self.game = game;
+    }
+
+    fn create(game : &mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let mut ret = Self{
+            widgets: vec![],
+            game,
+        };
+        let title = "SETTINGS";
+        ret.add_widget(LabelWidget::create(Alignment::CENTRE, -(render::text_width(title) as i32) / 2, 110, title, game), 0, 0);
+
+        // one line per option, in the order update reads them back
+        let display = if game.settings.fullscreen {DisplayMode::Fullscreen} else {DisplayMode::Windowed};
+        let scale = Scale(game.settings.scale.unwrap_or(0));
+        let master_volume = Volume::from(game.settings.master_volume);
+        let sfx_volume = Volume::from(game.settings.sfx_volume);
+        let reveal_delay = RevealDelay(game.settings.reveal_delay);
+        let colour_blind = Toggle::from(game.settings.colour_blind);
+        let transition = game.settings.transition;
+        let transition_time = TransitionTime(game.settings.transition_time);
+        let options : Vec<Box<dyn Widget>> = vec![
+            EnumWidget::create_labelled(Alignment::CENTRE, -100, 80, game, display, "DISPLAY      "),
+            EnumWidget::create_labelled(Alignment::CENTRE, -100, 65, game, scale, "SCALE        "),
+            EnumWidget::create_labelled(Alignment::CENTRE, -100, 50, game, master_volume, "MASTER VOLUME"),
+            EnumWidget::create_labelled(Alignment::CENTRE, -100, 35, game, sfx_volume, "SFX VOLUME   "),
+            EnumWidget::create_labelled(Alignment::CENTRE, -100, 20, game, reveal_delay, "REVEAL DELAY "),
+            EnumWidget::create_labelled(Alignment::CENTRE, -100, 5, game, colour_blind, "COLOUR BLIND "),
+            EnumWidget::create_labelled(Alignment::CENTRE, -100, -10, game, transition, "TRANSITION   "),
+            EnumWidget::create_labelled(Alignment::CENTRE, -100, -25, game, transition_time, "TRANS. LENGTH"),
+        ];
+        for (indx, option) in options.into_iter().enumerate() {
+            ret.add_widget(option, indx, OPTION_ROW);
+        }
+
+        // the names players start a match with, these save themselves once typed
+        for player in 0..MAX_PLAYERS {
+            ret.add_widget(NameWidget::create(Alignment::CENTRE, -100, -45 - 15 * player as i32, player, game), player, OPTION_ROW + 1);
+        }
+
+        ret.add_widget(LinkWidget::create(Alignment::LEFT, 20, -110, "< BACK", Link::Back, game), 1, 0);
+        let text = "RESOURCE PACKS >";
+        ret.add_widget(LinkWidget::create(Alignment::RIGHT, -20 - render::text_width(text) as i32, -110, text, Link::Packs, game), 2, 0);
+        Box::new(ret)
+    }
+
+    // copy the options into the settings, saving them if anything changed
+    fn update(&mut self) {
+        let game = unsafe { &mut *self.game };
+        let fullscreen = DisplayMode::get_from_index(self.option(0)) == DisplayMode::Fullscreen;
+        let scale = match Scale::get_from_index(self.option(1)) {
+            Scale(0) => {None}
+            Scale(scale) => {Some(scale)}
+        };
+        let master_volume = Volume::get_from_index(self.option(2)).get();
+        let sfx_volume = Volume::get_from_index(self.option(3)).get();
+        let reveal_delay = RevealDelay::get_from_index(self.option(4)).0;
+        let colour_blind = Toggle::get_from_index(self.option(5)) == Toggle::On;
+        let transition = TransitionStyle::get_from_index(self.option(6));
+        let transition_time = TransitionTime::get_from_index(self.option(7)).0;
+
+        let settings = &mut game.settings;
+        let changed = settings.fullscreen != fullscreen || settings.scale != scale || settings.master_volume != master_volume
+            || settings.sfx_volume != sfx_volume || settings.reveal_delay != reveal_delay || settings.colour_blind != colour_blind
+            || settings.transition != transition || settings.transition_time != transition_time;
+        if changed {
+            settings.fullscreen = fullscreen;
+            settings.scale = scale;
+            settings.master_volume = master_volume;
+            settings.sfx_volume = sfx_volume;
+            settings.reveal_delay = reveal_delay;
+            settings.colour_blind = colour_blind;
+            settings.transition = transition;
+            settings.transition_time = transition_time;
+            settings.save();
+        }
+    }
+}
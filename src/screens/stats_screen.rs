@@ -0,0 +1,93 @@
+use crate::game::Game;
+use crate::render;
+use crate::screen::Screen;
+use crate::widget::{Alignment, Widget};
+use crate::widgets::label_widget::LabelWidget;
+use crate::widgets::link_widget::{Link, LinkWidget};
+
+// the height of a line of text, with a gap under it
+const LINE_HEIGHT : i32 = render::FONT_SIZE.1 as i32 + 3;
+
+// the most entries shown in each list, so they fit on screen
+const MAX_ENTRIES : usize = 8;
+
+/// Shows the statistics saved between runs, reached from the main menu
+pub struct StatsScreen {
+    widgets : Vec<Vec<Box<dyn Widget>>>,
+    game : *mut Game,
+}
+
+impl StatsScreen {
+
+    // add a column of lines, starting at `y` and going down the screen
+    fn add_lines(&mut self, x : i32, y : i32, lines : Vec<String>, game : &mut Game) {
+        for (i, line) in lines.iter().enumerate() {
+            self.add_widget(LabelWidget::create(Alignment::CENTRE, x, y - LINE_HEIGHT * i as i32, line, game), 0, 0);
+        }
+    }
+}
+
+impl Screen for StatsScreen {
+    fn get_widgets(&mut self) -> &mut Vec<Vec<Box<dyn Widget>>> {
+        &mut self.widgets
+    }
+
+    fn get_game(&mut self) -> *mut Game {
+        self.game
+    }
+
+    fn set_game(&mut self, game: *mut Game) {
+        self.game = game;
+    }
+
+    fn create(game : &mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let mut ret = Self{
+            widgets: vec![],
+            game,
+        };
+        let title = "STATISTICS";
+        ret.add_widget(LabelWidget::create(Alignment::CENTRE, -(render::text_width(title) as i32) / 2, 110, title, game), 0, 0);
+
+        // general stats and the solo records on the left
+        let stats = &game.stats;
+        let mut left = vec![
+            format!("GAMES PLAYED   {}", stats.games_played),
+            format!("LONGEST STREAK {}", stats.longest_streak),
+            match stats.best_time {
+                Some(time) => {format!("BEST SOLO TIME {}S", time as u32)}
+                None => {String::from("BEST SOLO TIME -")}
+            },
+            String::new(),
+            String::from("FEWEST FLIPS"),
+        ];
+        let mut flips = stats.fewest_flips.iter().collect::<Vec<_>>();
+        flips.sort();
+        if flips.is_empty() {
+            left.push(String::from(" -"));
+        }
+        for (pairs, flips) in flips.iter().take(MAX_ENTRIES) {
+            left.push(format!(" {:>2} PAIRS  {}", pairs, flips));
+        }
+
+        // wins on the right, most first
+        let mut right = vec![String::from("WINS")];
+        let mut wins = stats.wins.iter().collect::<Vec<_>>();
+        wins.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        if wins.is_empty() {
+            right.push(String::from(" -"));
+        }
+        for (name, wins) in wins.iter().take(MAX_ENTRIES) {
+            right.push(format!(" {:<12} {}", name.to_uppercase(), wins));
+        }
+
+        ret.add_lines(-140, 85, left, game);
+        ret.add_lines(20, 85, right, game);
+
+        ret.add_widget(LinkWidget::create(Alignment::LEFT, 20, -110, "< BACK", Link::Back, game), 0, 1);
+        Box::new(ret)
+    }
+
+}
@@ -0,0 +1,468 @@
+use std::fs;
+use std::path::PathBuf;
+use log::{info, warn};
+use crate::utils;
+use crate::widgets::enum_widget::WidgetEnum;
+
+/// Options the player can change, loaded at startup and written back whenever they are changed on the settings screen
+pub struct Settings {
+    pub fullscreen : bool,
+    pub scale : Option<u32>, // the pixel scale, or None to fit the screen
+    pub master_volume : f32,
+    pub sfx_volume : f32,
+    pub reveal_delay : f32, // how long a picked pair stays face up, in seconds
+    pub player_names : Vec<String>, // names given to players at the start of a match, missing ones are "Player N"
+    pub colour_blind : bool, // show whose turn it is with a marker as well as colour
+    pub packs : Vec<String>, // the resource packs turned on, lowest priority first
+    pub transition : TransitionStyle, // the effect played when the screen changes
+    pub transition_time : f32 // how long it takes, in seconds
+}
+
+impl Default for Settings {
+
+    /// The settings used when there is no settings file
+    fn default() -> Self {
+        Self {
+            fullscreen: true,
+            scale: None,
+            master_volume: 1.0,
+            sfx_volume: 1.0,
+            reveal_delay: 2.0,
+            player_names: vec![],
+            colour_blind: false,
+            packs: vec![],
+            transition: TransitionStyle::Fade,
+            transition_time: 0.4
+        }
+    }
+}
+
+impl Settings {
+
+    /// Where the settings are kept, in the user data folder so they are found whatever folder the game is run from
+    pub fn path() -> PathBuf {
+        utils::user_data_dir().join("settings.json")
+    }
+
+    /// Load the settings file, or the defaults if there isn't one
+    pub fn load() -> Self {
+        match fs::read_to_string(Self::path()) {
+            Ok(json) => {Self::parse(&json)}
+            Err(_) => {
+                info!("No settings file, using the defaults");
+                Self::default()
+            }
+        }
+    }
+
+    /// Read settings written by [`Settings::to_json`]. Anything missing is left at its default
+    pub fn parse(json : &str) -> Self {
+        let mut settings = Self::default();
+        let get = |path| gjson::get(json, path);
+        if get("fullscreen").exists() {
+            settings.fullscreen = get("fullscreen").bool();
+        }
+        if get("scale").kind() == gjson::Kind::Number {
+            settings.scale = Some(get("scale").u32().max(1));
+        }
+        if get("master_volume").exists() {
+            settings.master_volume = get("master_volume").f32().clamp(0.0, 1.0);
+        }
+        if get("sfx_volume").exists() {
+            settings.sfx_volume = get("sfx_volume").f32().clamp(0.0, 1.0);
+        }
+        if get("reveal_delay").exists() {
+            settings.reveal_delay = get("reveal_delay").f32().max(0.0);
+        }
+        settings.player_names = get("player_names").array().iter().map(|name| name.to_string()).collect();
+        settings.colour_blind = get("colour_blind").bool();
+        settings.packs = get("packs").array().iter().map(|pack| pack.to_string()).collect();
+        if let Some(style) = TransitionStyle::parse(&get("transition").to_string()) {
+            settings.transition = style;
+        }
+        if get("transition_time").exists() {
+            settings.transition_time = get("transition_time").f32().max(0.0);
+        }
+        settings
+    }
+
+    /// Write the settings out as json
+    pub fn to_json(&self) -> String {
+        let names = self.player_names.iter().map(|name| json_string(name)).collect::<Vec<_>>().join(", ");
+        let packs = self.packs.iter().map(|pack| json_string(pack)).collect::<Vec<_>>().join(", ");
+        let scale = match self.scale {
+            Some(scale) => {scale.to_string()}
+            None => {String::from("null")}
+        };
+        format!("{{\n  \"fullscreen\" : {},\n  \"scale\" : {},\n  \"master_volume\" : {},\n  \"sfx_volume\" : {},\n  \"reveal_delay\" : {},\n  \"player_names\" : [{}],\n  \"colour_blind\" : {},\n  \"packs\" : [{}],\n  \"transition\" : \"{}\",\n  \"transition_time\" : {}\n}}\n",
+            self.fullscreen, scale, self.master_volume, self.sfx_volume, self.reveal_delay, names, self.colour_blind, packs,
+            self.transition.clone().get_as_string(), self.transition_time)
+    }
+
+    /// Write the settings back to the settings file
+    pub fn save(&self) {
+        let path = Self::path();
+        let result = match path.parent() {
+            Some(dir) => {fs::create_dir_all(dir).and_then(|_| fs::write(&path, self.to_json()))}
+            None => {fs::write(&path, self.to_json())}
+        };
+        if let Err(e) = result {
+            warn!("Couldn't save settings to {}: {}", path.display(), e);
+        }
+    }
+
+    /// The volume sound effects are played at
+    pub fn effect_volume(&self) -> f32 {
+        self.master_volume * self.sfx_volume
+    }
+
+    /// The name a player starts a match with, players are counted from 0
+    pub fn player_name(&self, player : usize) -> String {
+        match self.player_names.get(player) {
+            Some(name) if !name.is_empty() => {name.clone()}
+            _ => {format!("Player {}", player + 1)}
+        }
+    }
+}
+
+// a string written as json, with quotes, backslashes and control characters escaped
+fn json_string(value : &str) -> String {
+    let mut ret = String::from("\"");
+    for character in value.chars() {
+        match character {
+            '"' => {ret.push_str("\\\"")}
+            '\\' => {ret.push_str("\\\\")}
+            '\n' => {ret.push_str("\\n")}
+            '\t' => {ret.push_str("\\t")}
+            '\r' => {ret.push_str("\\r")}
+            c if (c as u32) < 0x20 => {ret.push_str(&format!("\\u{:04x}", c as u32))}
+            c => {ret.push(c)}
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+/// Whether the game fills the screen or sits in a window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisplayMode {
+    Fullscreen,
+    Windowed
+}
+
+impl WidgetEnum for DisplayMode {
+    fn get_as_string(&mut self) -> String {
+        match self {
+            DisplayMode::Fullscreen => {String::from("fullscreen")}
+            DisplayMode::Windowed => {String::from("windowed")}
+        }
+    }
+
+    fn get_from_index(index: usize) -> Self {
+        match index {
+            1 => {DisplayMode::Windowed}
+            _ => {DisplayMode::Fullscreen}
+        }
+    }
+
+    fn get_index(&mut self) -> usize {
+        match self {
+            DisplayMode::Fullscreen => {0}
+            DisplayMode::Windowed => {1}
+        }
+    }
+
+    fn count(&mut self) -> usize {
+        2
+    }
+
+    fn name(&mut self) -> String {
+        String::from("display_mode")
+    }
+}
+
+/// The pixel scale, `Scale(0)` fits the game to the screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scale(pub u32);
+
+// the biggest pixel scale offered
+const MAX_SCALE : u32 = 8;
+
+impl WidgetEnum for Scale {
+    fn get_as_string(&mut self) -> String {
+        if self.0 == 0 {
+            String::from("auto")
+        }
+        else {
+            format!("{}x", self.0)
+        }
+    }
+
+    fn get_from_index(index: usize) -> Self {
+        Scale(index as u32)
+    }
+
+    fn get_index(&mut self) -> usize {
+        self.0.min(MAX_SCALE) as usize
+    }
+
+    fn count(&mut self) -> usize {
+        MAX_SCALE as usize + 1
+    }
+
+    fn name(&mut self) -> String {
+        String::from("scale")
+    }
+}
+
+/// A volume in steps of 10%
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Volume(pub u32);
+
+impl Volume {
+    /// The volume as a multiplier, from 0 to 1
+    pub fn get(&self) -> f32 {
+        self.0 as f32 / 10.0
+    }
+
+    /// The nearest step to a multiplier
+    pub fn from(volume : f32) -> Self {
+        Volume((volume * 10.0).round().clamp(0.0, 10.0) as u32)
+    }
+}
+
+impl WidgetEnum for Volume {
+    fn get_as_string(&mut self) -> String {
+        format!("{}%", self.0 * 10)
+    }
+
+    fn get_from_index(index: usize) -> Self {
+        Volume(index.min(10) as u32)
+    }
+
+    fn get_index(&mut self) -> usize {
+        self.0 as usize
+    }
+
+    fn count(&mut self) -> usize {
+        11
+    }
+
+    fn name(&mut self) -> String {
+        String::from("volume")
+    }
+}
+
+// the reveal delays offered, in seconds
+const REVEAL_DELAYS : [f32; 6] = [0.5, 1.0, 1.5, 2.0, 2.5, 3.0];
+
+/// How long a picked pair stays face up, one of a few steps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RevealDelay(pub f32);
+
+impl WidgetEnum for RevealDelay {
+    fn get_as_string(&mut self) -> String {
+        format!("{:.1}s", self.0)
+    }
+
+    fn get_from_index(index: usize) -> Self {
+        RevealDelay(REVEAL_DELAYS[index.min(REVEAL_DELAYS.len() - 1)])
+    }
+
+    fn get_index(&mut self) -> usize {
+        // the closest step, in case the file has a value in between
+        let delay = self.0;
+        (0..REVEAL_DELAYS.len()).min_by(|a, b| (REVEAL_DELAYS[*a] - delay).abs().total_cmp(&(REVEAL_DELAYS[*b] - delay).abs())).unwrap_or(0)
+    }
+
+    fn count(&mut self) -> usize {
+        REVEAL_DELAYS.len()
+    }
+
+    fn name(&mut self) -> String {
+        String::from("reveal_delay")
+    }
+}
+
+/// An option that is on or off
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Toggle {
+    Off,
+    On
+}
+
+impl Toggle {
+    pub fn from(on : bool) -> Self {
+        if on {Toggle::On} else {Toggle::Off}
+    }
+}
+
+impl WidgetEnum for Toggle {
+    fn get_as_string(&mut self) -> String {
+        match self {
+            Toggle::Off => {String::from("off")}
+            Toggle::On => {String::from("on")}
+        }
+    }
+
+    fn get_from_index(index: usize) -> Self {
+        match index {
+            1 => {Toggle::On}
+            _ => {Toggle::Off}
+        }
+    }
+
+    fn get_index(&mut self) -> usize {
+        match self {
+            Toggle::Off => {0}
+            Toggle::On => {1}
+        }
+    }
+
+    fn count(&mut self) -> usize {
+        2
+    }
+
+    fn name(&mut self) -> String {
+        String::from("toggle")
+    }
+}
+
+/// The effect played when the screen changes, like going from the menu into a match
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransitionStyle {
+    /// Cut straight to the new screen
+    Off,
+    /// Fade to black and back
+    Fade,
+    /// Slide a black panel across
+    Slide,
+    /// Cover the screen in black squares, a few at a time
+    Dissolve
+}
+
+impl TransitionStyle {
+    pub fn parse(val : &str) -> Option<Self> {
+        (0..4).map(Self::get_from_index).find(|style| style.clone().get_as_string() == val)
+    }
+}
+
+impl WidgetEnum for TransitionStyle {
+    fn get_as_string(&mut self) -> String {
+        match self {
+            TransitionStyle::Off => {String::from("off")}
+            TransitionStyle::Fade => {String::from("fade")}
+            TransitionStyle::Slide => {String::from("slide")}
+            TransitionStyle::Dissolve => {String::from("dissolve")}
+        }
+    }
+
+    fn get_from_index(index: usize) -> Self {
+        match index {
+            1 => {TransitionStyle::Fade}
+            2 => {TransitionStyle::Slide}
+            3 => {TransitionStyle::Dissolve}
+            _ => {TransitionStyle::Off}
+        }
+    }
+
+    fn get_index(&mut self) -> usize {
+        match self {
+            TransitionStyle::Off => {0}
+            TransitionStyle::Fade => {1}
+            TransitionStyle::Slide => {2}
+            TransitionStyle::Dissolve => {3}
+        }
+    }
+
+    fn count(&mut self) -> usize {
+        4
+    }
+
+    fn name(&mut self) -> String {
+        String::from("transition")
+    }
+}
+
+// the transition lengths offered, in seconds
+const TRANSITION_TIMES : [f32; 5] = [0.2, 0.4, 0.6, 0.8, 1.0];
+
+/// How long a screen transition takes, one of a few steps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransitionTime(pub f32);
+
+impl WidgetEnum for TransitionTime {
+    fn get_as_string(&mut self) -> String {
+        format!("{:.1}s", self.0)
+    }
+
+    fn get_from_index(index: usize) -> Self {
+        TransitionTime(TRANSITION_TIMES[index.min(TRANSITION_TIMES.len() - 1)])
+    }
+
+    fn get_index(&mut self) -> usize {
+        // the closest step, in case the file has a value in between
+        let time = self.0;
+        (0..TRANSITION_TIMES.len()).min_by(|a, b| (TRANSITION_TIMES[*a] - time).abs().total_cmp(&(TRANSITION_TIMES[*b] - time).abs())).unwrap_or(0)
+    }
+
+    fn count(&mut self) -> usize {
+        TRANSITION_TIMES.len()
+    }
+
+    fn name(&mut self) -> String {
+        String::from("transition_time")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let settings = Settings {
+            fullscreen: false,
+            scale: Some(3),
+            master_volume: 0.55,
+            sfx_volume: 0.8,
+            reveal_delay: 1.5,
+            player_names: vec![String::from("Ann"), String::from("Bo \"B\" \\"), String::from("tab\tnew\nline\u{1}")],
+            colour_blind: true,
+            packs: vec![String::from("zipped.zip"), String::from("folder")],
+            transition: TransitionStyle::Dissolve,
+            transition_time: 0.6
+        };
+        let read = Settings::parse(&settings.to_json());
+        assert!(!read.fullscreen);
+        assert_eq!(read.scale, Some(3));
+        assert_eq!(read.master_volume, 0.55);
+        assert_eq!(read.sfx_volume, 0.8);
+        assert_eq!(read.reveal_delay, 1.5);
+        assert_eq!(read.player_names, settings.player_names);
+        assert!(read.colour_blind);
+        assert_eq!(read.packs, settings.packs);
+        assert_eq!(read.transition, TransitionStyle::Dissolve);
+        assert_eq!(read.transition_time, 0.6);
+    }
+
+    #[test]
+    fn defaults_round_trip() {
+        let read = Settings::parse(&Settings::default().to_json());
+        let default = Settings::default();
+        assert_eq!(read.fullscreen, default.fullscreen);
+        assert_eq!(read.scale, None);
+        assert_eq!(read.reveal_delay, default.reveal_delay);
+        assert_eq!(read.transition, default.transition);
+    }
+
+    #[test]
+    fn missing_and_bad_values_use_the_defaults() {
+        let read = Settings::parse("{\"master_volume\" : 3.0, \"reveal_delay\" : -1, \"transition\" : \"spin\"}");
+        assert!(read.fullscreen);
+        assert_eq!(read.master_volume, 1.0);
+        assert_eq!(read.reveal_delay, 0.0);
+        assert_eq!(read.transition, TransitionStyle::Fade);
+        assert_eq!(read.player_name(0), "Player 1");
+    }
+}
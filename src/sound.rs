@@ -1,7 +1,8 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
+use std::io::Cursor;
+use std::sync::Arc;
 use std::sync::Mutex;
+use log::warn;
 use once_cell::sync::OnceCell;
 #[cfg(not(target_os = "emscripten"))]
 use rodio::{Decoder, OutputStream, OutputStreamHandle, Source};
@@ -11,7 +12,7 @@ use crate::resource_location::ResourceLocation;
 
 /// Used to hold information about a sound
 pub struct Sound {
-    pub path : String,
+    pub data : Arc<[u8]>, // the whole file, so sounds can come from inside an archive
     pub resource_location : ResourceLocation
 }
 
@@ -48,14 +49,20 @@ impl AudioManager {
 
     }
 
-    /// Plays sound given the sound map and resource location.
-    pub fn play_sound(&self, sound : &Sound) {
+    /// Plays sound given the sound map and resource location, at a volume from 0 to 1.
+    pub fn play_sound(&self, sound : &Sound, volume : f32) {
         #[cfg(not(target_os = "emscripten"))]
         if self.stream.is_some() && self.stream_handle.is_some() {
             // use data within the Sound type to get playable data
-            let sound_data = Decoder::new(BufReader::new(File::open(&sound.path).unwrap())).unwrap();
+            let sound_data = match Decoder::new(Cursor::new(sound.data.clone())) {
+                Ok(sound_data) => {sound_data}
+                Err(e) => {
+                    warn!("Sound {} could not be played: {}", sound.resource_location.to_string(), e);
+                    return
+                }
+            };
             // play the sound
-            self.stream_handle.clone().unwrap().play_raw(sound_data.convert_samples()).expect("Something went wrong with audio playback");
+            self.stream_handle.clone().unwrap().play_raw(sound_data.convert_samples().amplify(volume)).expect("Something went wrong with audio playback");
         }
 
 
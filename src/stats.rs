@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use log::{info, warn};
+use crate::utils;
+
+/// Statistics kept between runs of the game, saved as json in the user data folder
+pub struct Stats {
+    pub games_played : u32,
+    pub wins : HashMap<String, u32>, // player name to the number of matches they have won
+    pub best_time : Option<f32>, // the fastest solo match, in seconds
+    pub fewest_flips : HashMap<u32, u32>, // board size in pairs to the fewest flips a solo match has been cleared in
+    pub longest_streak : u32 // the most pairs found in a row by one player without a miss
+}
+
+impl Stats {
+
+    /// Statistics for someone who hasn't played yet
+    pub fn empty() -> Self {
+        Self {
+            games_played: 0,
+            wins: HashMap::new(),
+            best_time: None,
+            fewest_flips: HashMap::new(),
+            longest_streak: 0
+        }
+    }
+
+    /// Where the statistics are saved
+    pub fn path() -> PathBuf {
+        utils::user_data_dir().join("stats.json")
+    }
+
+    /// Load the saved statistics, or start fresh if there aren't any
+    pub fn load() -> Self {
+        match fs::read_to_string(Self::path()) {
+            Ok(json) => {Self::parse(&json)}
+            Err(_) => {
+                info!("No statistics saved yet, starting fresh");
+                Self::empty()
+            }
+        }
+    }
+
+    /// Read statistics written by [`Stats::to_json`]
+    pub fn parse(json : &str) -> Self {
+        let mut stats = Self::empty();
+        stats.games_played = gjson::get(json, "games_played").u32();
+        stats.longest_streak = gjson::get(json, "longest_streak").u32();
+        let best_time = gjson::get(json, "best_time");
+        if best_time.kind() == gjson::Kind::Number {
+            stats.best_time = Some(best_time.f32());
+        }
+        gjson::get(json, "wins").each(|name, wins| {
+            stats.wins.insert(name.to_string(), wins.u32());
+            true
+        });
+        gjson::get(json, "fewest_flips").each(|pairs, flips| {
+            match pairs.to_string().parse::<u32>() {
+                Ok(pairs) => {stats.fewest_flips.insert(pairs, flips.u32());}
+                Err(_) => {warn!("Ignoring fewest flips for a board of {} pairs", pairs)}
+            }
+            true
+        });
+        stats
+    }
+
+    /// Write the statistics out as json
+    pub fn to_json(&self) -> String {
+        let mut wins = self.wins.iter().collect::<Vec<_>>();
+        wins.sort();
+        let wins = wins.iter().map(|(name, wins)| format!("\"{}\":{}", name.replace('\\', "\\\\").replace('"', "\\\""), wins)).collect::<Vec<_>>().join(",");
+        let mut flips = self.fewest_flips.iter().collect::<Vec<_>>();
+        flips.sort();
+        let flips = flips.iter().map(|(pairs, flips)| format!("\"{}\":{}", pairs, flips)).collect::<Vec<_>>().join(",");
+        let best_time = match self.best_time {
+            Some(time) => {format!("{:.3}", time)}
+            None => {String::from("null")}
+        };
+        format!("{{\"type\":\"stats\",\"games_played\":{},\"wins\":{{{}}},\"best_time\":{},\"fewest_flips\":{{{}}},\"longest_streak\":{}}}\n",
+            self.games_played, wins, best_time, flips, self.longest_streak)
+    }
+
+    /// Save the statistics to the user data folder
+    pub fn save(&self) {
+        let path = Self::path();
+        let result = match path.parent() {
+            Some(dir) => {fs::create_dir_all(dir).and_then(|_| fs::write(&path, self.to_json()))}
+            None => {fs::write(&path, self.to_json())}
+        };
+        if let Err(e) = result {
+            warn!("Couldn't save statistics to {}: {}", path.display(), e);
+        }
+    }
+
+    /// Add a finished solo match, keeping the best time and flip count for its board size
+    pub fn record_solo(&mut self, time : f32, flips : u32, pairs : u32, streak : u32) {
+        self.games_played += 1;
+        self.longest_streak = self.longest_streak.max(streak);
+        if self.best_time.is_none_or(|best| time < best) {
+            self.best_time = Some(time);
+        }
+        let fewest = self.fewest_flips.entry(pairs).or_insert(flips);
+        *fewest = (*fewest).min(flips);
+    }
+
+    /// Add a finished match between players, `winner` is `None` on a draw
+    pub fn record_match(&mut self, winner : Option<String>, streak : u32) {
+        self.games_played += 1;
+        self.longest_streak = self.longest_streak.max(streak);
+        if let Some(name) = winner {
+            *self.wins.entry(name).or_insert(0) += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut stats = Stats::empty();
+        stats.record_solo(42.5, 30, 8, 3);
+        stats.record_solo(50.0, 24, 8, 5);
+        stats.record_solo(20.0, 10, 4, 1);
+        stats.record_match(Some(String::from("Ann")), 2);
+        stats.record_match(Some(String::from("Bo \"B\"")), 1);
+        stats.record_match(Some(String::from("Ann")), 0);
+        stats.record_match(None, 0);
+
+        let read = Stats::parse(&stats.to_json());
+        assert_eq!(read.games_played, 7);
+        assert_eq!(read.best_time, Some(20.0));
+        assert_eq!(read.fewest_flips, HashMap::from([(8, 24), (4, 10)]));
+        assert_eq!(read.wins, HashMap::from([(String::from("Ann"), 2), (String::from("Bo \"B\""), 1)]));
+        assert_eq!(read.longest_streak, 5);
+    }
+
+    #[test]
+    fn empty_round_trip() {
+        let read = Stats::parse(&Stats::empty().to_json());
+        assert_eq!(read.games_played, 0);
+        assert_eq!(read.best_time, None);
+        assert!(read.wins.is_empty());
+        assert!(read.fewest_flips.is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use std::cmp::PartialEq;
-use std::collections::HashMap;
+use crate::assets::Registry;
 use sdl2::keyboard::Scancode::S;
 use sdl2::rect::Rect;
 use sdl2::render::{Canvas, Texture, WindowCanvas};
@@ -98,6 +98,14 @@ impl WidgetEnum for TileSize {
         }
     }
 
+    fn get_index(&mut self) -> usize {
+        match self {
+            TileSize::SMALL => {0}
+            TileSize::MEDIUM => {1}
+            TileSize::BIG => {2}
+        }
+    }
+
     fn count(&mut self) -> usize {
         3usize
     }
@@ -231,7 +239,7 @@ impl Tile {
         ((half_x - px) + x, (half_y - py ) + y)
     }
 
-    pub fn render(&self, texture: &HashMap<String,Texture>, coords : (i32, i32), canvas: &mut WindowCanvas, sf : i32, player_coords :  (f32, f32)) {
+    pub fn render(&self, texture: &Registry<Texture>, coords : (i32, i32), canvas: &mut WindowCanvas, sf : i32, player_coords :  (f32, f32)) {
         let screen = self.screen(coords, player_coords);
         render::draw_pp_texture(screen.0, screen.1, &self.asset_data, canvas, sf, texture)
 
@@ -0,0 +1,106 @@
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{BlendMode, WindowCanvas};
+use crate::render;
+use crate::screen::ScreenCommand;
+use crate::settings::TransitionStyle;
+use crate::tween::Easing;
+
+// the size of the squares a dissolve is made of
+const DISSOLVE_BLOCK : u32 = 8;
+
+/// An effect played over the game when the screen changes. It covers the old screen, the screen changes are made once it is
+/// covered, then it uncovers the new one. Widgets can't be clicked while it plays
+pub struct Transition {
+    style : TransitionStyle,
+    duration : f32, // in seconds, covering and uncovering
+    elapsed : f32,
+    pending : Vec<ScreenCommand> // the screen changes made halfway through
+}
+
+impl Transition {
+    pub fn create(style : TransitionStyle, duration : f32, pending : Vec<ScreenCommand>) -> Self {
+        Self {
+            style,
+            duration,
+            elapsed: 0.0,
+            pending
+        }
+    }
+
+    /// Move the transition on by `delta` seconds
+    pub fn tick(&mut self, delta : f32) {
+        self.elapsed = (self.elapsed + delta).min(self.duration);
+    }
+
+    // how far through it is, from 0 to 1
+    fn progress(&self) -> f32 {
+        if self.duration > 0.0 {self.elapsed / self.duration} else {1.0}
+    }
+
+    /// Whether the old screen has been covered, so the screen can be changed
+    pub fn is_covered(&self) -> bool {
+        self.progress() >= 0.5
+    }
+
+    /// Whether it has finished
+    pub fn is_done(&self) -> bool {
+        self.progress() >= 1.0
+    }
+
+    /// Add more screen changes, made along with the rest once the screen is covered
+    pub fn add(&mut self, commands : Vec<ScreenCommand>) {
+        self.pending.extend(commands);
+    }
+
+    /// Take the screen changes that are waiting to be made
+    pub fn take_pending(&mut self) -> Vec<ScreenCommand> {
+        std::mem::take(&mut self.pending)
+    }
+
+    /// Draw the effect over the game area
+    pub fn render(&self, canvas : &mut WindowCanvas, sf : i32) {
+        let offset = render::get_viewport().lock().unwrap().get_offset();
+        let dims = render::get_actual_dimensions().lock().unwrap().get();
+        let progress = self.progress();
+        // how much of the screen is covered, going up to all of it halfway through and back down
+        let cover = 1.0 - (1.0 - progress * 2.0).abs();
+
+        canvas.set_scale(sf as f32, sf as f32).expect("TODO: panic message");
+        canvas.set_draw_color(Color::RGB(0, 0, 0));
+        match self.style {
+            TransitionStyle::Off => {}
+            TransitionStyle::Fade => {
+                let alpha = (Easing::EaseIn.apply(cover) * 255.0) as u8;
+                canvas.set_blend_mode(BlendMode::Blend);
+                canvas.set_draw_color(Color::RGBA(0, 0, 0, alpha));
+                let _ = canvas.fill_rect(Rect::new(offset.0, offset.1, dims.0, dims.1));
+                canvas.set_blend_mode(BlendMode::None);
+            }
+            TransitionStyle::Slide => {
+                // comes in from the right and carries on out to the left
+                let width = (Easing::EaseInOut.apply(cover) * dims.0 as f32).round() as u32;
+                let x = if progress < 0.5 {dims.0 - width} else {0};
+                if width > 0 {
+                    let _ = canvas.fill_rect(Rect::new(offset.0 + x as i32, offset.1, width, dims.1));
+                }
+            }
+            TransitionStyle::Dissolve => {
+                for bx in 0..dims.0.div_ceil(DISSOLVE_BLOCK) {
+                    for by in 0..dims.1.div_ceil(DISSOLVE_BLOCK) {
+                        if block_order(bx, by) < cover {
+                            let _ = canvas.fill_rect(Rect::new(offset.0 + (bx * DISSOLVE_BLOCK) as i32, offset.1 + (by * DISSOLVE_BLOCK) as i32, DISSOLVE_BLOCK, DISSOLVE_BLOCK));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// when a dissolve block is filled in, from 0 to 1. Scrambled so the blocks look random, but the same every frame
+fn block_order(x : u32, y : u32) -> f32 {
+    let hash = x.wrapping_mul(73856093) ^ y.wrapping_mul(19349663);
+    let hash = (hash ^ (hash >> 13)).wrapping_mul(0x5bd1e995);
+    (hash % 1000) as f32 / 1000.0
+}
@@ -0,0 +1,257 @@
+/// The shape of a tween's motion, taking how far through it is from 0 to 1 and giving how far along the value should be
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    /// The same speed the whole way
+    Linear,
+    /// Starts slowly and speeds up
+    EaseIn,
+    /// Starts quickly and slows down at the end
+    EaseOut,
+    /// Slow at both ends
+    EaseInOut,
+    /// Goes a little past the end and settles back
+    Back,
+    /// Drops onto the end and bounces a few times
+    Bounce
+}
+
+impl Easing {
+    pub fn apply(&self, t : f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => {t}
+            Easing::EaseIn => {t * t * t}
+            Easing::EaseOut => {1.0 - (1.0 - t).powi(3)}
+            Easing::EaseInOut => {
+                if t < 0.5 {4.0 * t * t * t} else {1.0 - (2.0 - 2.0 * t).powi(3) / 2.0}
+            }
+            Easing::Back => {
+                // how far past the end it goes
+                let overshoot = 1.70158;
+                1.0 + (overshoot + 1.0) * (t - 1.0).powi(3) + overshoot * (t - 1.0).powi(2)
+            }
+            Easing::Bounce => {
+                // four arcs, each smaller than the last
+                let (n, d) = (7.5625, 2.75);
+                if t < 1.0 / d {
+                    n * t * t
+                }
+                else if t < 2.0 / d {
+                    let t = t - 1.5 / d;
+                    n * t * t + 0.75
+                }
+                else if t < 2.5 / d {
+                    let t = t - 2.25 / d;
+                    n * t * t + 0.9375
+                }
+                else {
+                    let t = t - 2.625 / d;
+                    n * t * t + 0.984375
+                }
+            }
+        }
+    }
+}
+
+/// A value that can be tweened, by working out the point a fraction `t` of the way from one value to another
+pub trait Lerp : Copy {
+    fn lerp(from : Self, to : Self, t : f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(from : Self, to : Self, t : f32) -> Self {
+        from + (to - from) * t
+    }
+}
+
+impl Lerp for (f32, f32) {
+    fn lerp(from : Self, to : Self, t : f32) -> Self {
+        (f32::lerp(from.0, to.0, t), f32::lerp(from.1, to.1, t))
+    }
+}
+
+impl Lerp for (i32, i32) {
+    fn lerp(from : Self, to : Self, t : f32) -> Self {
+        (f32::lerp(from.0 as f32, to.0 as f32, t).round() as i32, f32::lerp(from.1 as f32, to.1 as f32, t).round() as i32)
+    }
+}
+
+/// Moves a value from one point to another over time, like entity coordinates, widget coordinates, scale or alpha.
+/// Call [`Tween::tick`] every frame with the delta and use what it gives back
+pub struct Tween<T : Lerp> {
+    from : T,
+    to : T,
+    duration : f32, // in seconds
+    elapsed : f32,
+    easing : Easing,
+    on_complete : Option<Box<dyn FnOnce()>> // run once, the frame the tween reaches the end
+}
+
+impl<T : Lerp> Tween<T> {
+
+    /// Create a tween that starts straight away
+    pub fn create(from : T, to : T, duration : f32, easing : Easing) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            elapsed: 0.0,
+            easing,
+            on_complete: None
+        }
+    }
+
+    /// A tween that has already finished, resting at `value`
+    pub fn still(value : T) -> Self {
+        Self::create(value, value, 0.0, Easing::Linear)
+    }
+
+    /// Wait before starting, like cards being dealt one after another
+    pub fn delay(mut self, seconds : f32) -> Self {
+        self.elapsed = -seconds;
+        self
+    }
+
+    /// Run something once the tween is finished
+    pub fn on_complete(mut self, callback : impl FnOnce() + 'static) -> Self {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Move the tween on by `delta` seconds, returning the new value
+    pub fn tick(&mut self, delta : f32) -> T {
+        self.elapsed = (self.elapsed + delta).min(self.duration);
+        if self.is_done() {
+            if let Some(callback) = self.on_complete.take() {
+                callback();
+            }
+        }
+        self.value()
+    }
+
+    /// The value at this point in the tween
+    pub fn value(&self) -> T {
+        let t = if self.duration > 0.0 {self.elapsed.max(0.0) / self.duration} else {1.0};
+        T::lerp(self.from, self.to, self.easing.apply(t))
+    }
+
+    /// Where the tween ends up
+    pub fn target(&self) -> T {
+        self.to
+    }
+
+    /// Whether the tween has reached the end
+    pub fn is_done(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+/// Plays a tween from 0 to 1 when something is shown and drops back to 0 when it is hidden again,
+/// for widgets that appear partway through a screen like the buttons at the end of a match
+pub struct Appear {
+    tween : Tween<f32>,
+    shown : bool,
+    duration : f32,
+    easing : Easing
+}
+
+impl Appear {
+    pub fn create(duration : f32, easing : Easing) -> Self {
+        Self {
+            tween: Tween::still(0.0),
+            shown: false,
+            duration,
+            easing
+        }
+    }
+
+    /// Move on by `delta` seconds, starting again from 0 if it has just been shown. Gives how far it has appeared
+    pub fn update(&mut self, shown : bool, delta : f32) -> f32 {
+        if shown && !self.shown {
+            self.tween = Tween::create(0.0, 1.0, self.duration, self.easing);
+        }
+        else if !shown {
+            self.tween = Tween::still(0.0);
+        }
+        self.shown = shown;
+        self.tween.tick(delta)
+    }
+
+    /// How far it has appeared, from 0 to 1. Some easings go a little past 1 before settling
+    pub fn value(&self) -> f32 {
+        self.tween.value()
+    }
+
+    /// The value as an alpha, to fade in with
+    pub fn alpha(&self) -> u8 {
+        (self.value().clamp(0.0, 1.0) * 255.0) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    const EASINGS : [Easing; 6] = [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut, Easing::Back, Easing::Bounce];
+
+    fn close(a : f32, b : f32) -> bool {
+        (a - b).abs() < 0.0001
+    }
+
+    #[test]
+    fn easings_start_at_0_and_end_at_1() {
+        for easing in EASINGS {
+            assert!(close(easing.apply(0.0), 0.0), "{:?} starts at {}", easing, easing.apply(0.0));
+            assert!(close(easing.apply(1.0), 1.0), "{:?} ends at {}", easing, easing.apply(1.0));
+            // outside 0 to 1 is clamped
+            assert!(close(easing.apply(-1.0), 0.0) && close(easing.apply(2.0), 1.0), "{:?} isn't clamped", easing);
+        }
+    }
+
+    #[test]
+    fn holds_the_start_during_the_delay() {
+        let mut tween = Tween::create(10.0, 20.0, 1.0, Easing::Linear).delay(0.5);
+        assert_eq!(tween.value(), 10.0);
+        assert_eq!(tween.tick(0.25), 10.0);
+        assert_eq!(tween.tick(0.25), 10.0);
+        assert!(!tween.is_done());
+        assert!(close(tween.tick(0.5), 15.0));
+    }
+
+    #[test]
+    fn clamps_once_finished() {
+        let mut tween = Tween::create((0, 0), (10, -10), 1.0, Easing::Back);
+        tween.tick(0.6);
+        assert_eq!(tween.tick(5.0), (10, -10));
+        assert!(tween.is_done());
+        assert_eq!(tween.tick(5.0), (10, -10));
+        assert_eq!(tween.target(), (10, -10));
+        assert_eq!(Tween::still(3.0).value(), 3.0);
+    }
+
+    #[test]
+    fn on_complete_runs_once() {
+        let runs = Rc::new(Cell::new(0));
+        let counter = runs.clone();
+        let mut tween = Tween::create(0.0, 1.0, 0.5, Easing::EaseOut).on_complete(move || counter.set(counter.get() + 1));
+        tween.tick(0.25);
+        assert_eq!(runs.get(), 0);
+        for _ in 0..5 {
+            tween.tick(0.25);
+        }
+        assert_eq!(runs.get(), 1);
+    }
+
+    #[test]
+    fn appear_restarts_when_shown_again() {
+        let mut appear = Appear::create(1.0, Easing::Linear);
+        assert_eq!(appear.update(false, 0.5), 0.0);
+        assert!(close(appear.update(true, 0.5), 0.5));
+        assert_eq!(appear.update(true, 1.0), 1.0);
+        assert_eq!(appear.alpha(), 255);
+        assert_eq!(appear.update(false, 0.1), 0.0);
+        assert!(close(appear.update(true, 0.25), 0.25));
+    }
+}
@@ -77,13 +77,13 @@ fn sort(list : &mut Vec<(usize, usize, f32)>) {
     }
 }
 
-pub fn init_logger() {
+/// Start logging to a file in the log folder of the user data folder, and to the console at `level`
+pub fn init_logger(level : LevelFilter) {
 
     // following code from https://github.com/estk/log4rs/blob/main/examples/log_to_file.rs
     // slightly edited
 
-    let level = log::LevelFilter::Info;
-    let file_path = format!("log/{:}.log", chrono::offset::Local::now().to_string().replace(" ", "_").replace(":", "-"));
+    let file_path = user_data_dir().join("log").join(format!("{:}.log", chrono::offset::Local::now().to_string().replace(" ", "_").replace(":", "-")));
 
     // Build a stderr logger.
     let stderr = ConsoleAppender::builder().target(Target::Stderr).build();
@@ -130,3 +130,23 @@ pub fn get_dist(a : &(f32, f32), b : &(f32, f32)) -> u32 {
     u32::sqrt(&(c_squared as u32))
 }
 
+
+/// The seed everyone gets today, so the same board can be played by everyone on the same day. It is the UTC date written as a number, like 20240131, so every time zone gets the same board
+pub fn daily_seed() -> u64 {
+    chrono::Utc::now().format("%Y%m%d").to_string().parse().unwrap()
+}
+
+/// The folder the game keeps its saved data in, like statistics. Follows the usual place for each platform, falling back to the working directory
+pub fn user_data_dir() -> std::path::PathBuf {
+    let base = if cfg!(target_os = "windows") {
+        std::env::var("APPDATA").ok().map(std::path::PathBuf::from)
+    }
+    else {
+        std::env::var("XDG_DATA_HOME").ok().filter(|dir| !dir.is_empty()).map(std::path::PathBuf::from)
+            .or(std::env::var("HOME").ok().map(|home| std::path::Path::new(&home).join(".local/share")))
+    };
+    match base {
+        Some(base) => {base.join("memory_game")}
+        None => {std::path::PathBuf::from(".")}
+    }
+}
@@ -1,10 +1,11 @@
-use std::collections::HashMap;
+use crate::assets::Registry;
 use log::warn;
+use sdl2::event::Event;
 use sdl2::rect::Rect;
 use sdl2::render::{Texture, WindowCanvas};
 use crate::game::Game;
 use crate::render;
-use crate::render::AssetData;
+use crate::render::{AssetData, Transform};
 use crate::resource_location::ResourceLocation;
 
 /// Used to create buttons and widget UI displays.
@@ -78,12 +79,24 @@ pub trait Widget {
         None
     }
 
-    fn render(&mut self, textures : &HashMap<String, Texture>, sf : i32, canvas : &mut WindowCanvas, debug : bool) {
+    /// Called with every input event while the widget's screen is on top, for widgets that take typing
+    fn on_event(&mut self, _event : &Event) {}
+
+    /// Called every frame with the time since the last one, for widgets that animate
+    fn tick(&mut self, _delta : f32) {}
+
+    /// How solid the widget is drawn, from 0 for invisible to 255
+    fn get_alpha(&mut self) -> u8 {
+        255
+    }
+
+    fn render(&mut self, textures : &Registry<Texture>, sf : i32, canvas : &mut WindowCanvas, debug : bool) {
         let coords = self.correct_coords();
         if debug {
             render::draw_pp_texture(coords.0, coords.1, &self.get_debug_asset_data(), canvas, sf, textures)
         }
-        render::draw_pp_texture(coords.0, coords.1, &self.get_asset_data(), canvas, sf, textures)
+        let transform = Transform { alpha: self.get_alpha(), ..Transform::at(coords.0, coords.1) };
+        render::draw_transformed_texture(&transform, &self.get_asset_data(), canvas, sf, textures)
     }
 }
 
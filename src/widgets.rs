@@ -8,6 +8,16 @@ pub(crate) mod player_widget;
 pub(crate) mod play_again_widget;
 pub(crate) mod end_screen_quit_widget;
 pub(crate) mod crown_widget;
+pub(crate) mod label_widget;
+pub(crate) mod deck_widget;
+pub(crate) mod board_size_widget;
+pub(crate) mod players_widget;
+pub(crate) mod result_widget;
+pub(crate) mod opponent_widget;
+pub(crate) mod seed_widget;
+pub(crate) mod link_widget;
+pub(crate) mod pack_widget;
+pub(crate) mod name_widget;
 
 use crate::widget::Widget;
 
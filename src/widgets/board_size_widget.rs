@@ -0,0 +1,136 @@
+use crate::assets::Registry;
+use sdl2::rect::Rect;
+use sdl2::render::{Texture, WindowCanvas};
+use crate::board::BoardLayout;
+use crate::game::Game;
+use crate::render;
+use crate::render::AssetData;
+use crate::resource_location::ResourceLocation;
+use crate::widget::{Alignment, Widget};
+
+// the board sizes offered, in pairs. Sizes bigger than the chosen deck are left out
+const PAIR_OPTIONS : [u32; 6] = [3, 6, 9, 12, 15, 18];
+
+/// Cycles through the board sizes the chosen deck can fill when clicked, like an [`EnumWidget`]
+///
+/// [`EnumWidget`]: crate::widgets::enum_widget::EnumWidget
+pub struct BoardSizeWidget {
+    selected : bool,
+    alignment: Alignment,
+    coords : (i32, i32),
+    game : *mut Game
+}
+
+impl BoardSizeWidget {
+    pub fn create(alignment: Alignment, x : i32, y : i32, game : *mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let ret = Self {
+            selected: false,
+            alignment,
+            coords: (x, y),
+            game
+        };
+        Box::new(ret)
+    }
+
+    // the pair counts the chosen deck can fill, along with the one currently picked
+    fn options(&mut self) -> (Vec<u32>, u32) {
+        let game = unsafe { &mut *self.game };
+        let deck = match game.deck.as_ref().and_then(|rl| game.assets.decks.find(rl)) {
+            Some(deck) => {deck}
+            None => {return (vec![], 0)}
+        };
+        let available = deck.pool(&game.assets.cards).len() as u32;
+        let default = deck.get_pairs(&game.assets.cards);
+
+        let mut options = PAIR_OPTIONS.iter().copied().filter(|p| *p <= available).collect::<Vec<_>>();
+        if !options.contains(&default) {
+            options.push(default);
+            options.sort();
+        }
+        (options, game.pair_count.unwrap_or(default))
+    }
+
+    fn text(&mut self) -> String {
+        let (_, pairs) = self.options();
+        let layout = BoardLayout::for_cards(pairs as usize * 2);
+        format!("< {} PAIRS ({}X{}) >", pairs, layout.columns, layout.rows)
+    }
+}
+
+impl Widget for BoardSizeWidget {
+
+    // Cycle through the board sizes when clicked
+    fn on_click(&mut self) {
+        let (options, current) = self.options();
+        if options.is_empty() {
+            return
+        }
+        let indx = options.iter().position(|p| *p == current).unwrap_or(0);
+        let next = options[(indx + 1) % options.len()];
+        unsafe {
+            (*self.game).pair_count = Some(next);
+            (*self.game).play_sound(ResourceLocation::new("memory_game", "sounds/pop.ogg"));
+        }
+    }
+
+    fn get_selected(&mut self) -> bool {
+        self.selected
+    }
+
+    fn set_selected(&mut self, tf: bool) {
+        self.selected = tf
+    }
+
+    fn get_screen_coordinates(&mut self) -> (i32, i32) {
+        self.coords
+    }
+
+    fn set_screen_coordinates(&mut self, x: i32, y: i32) {
+        self.coords = (x, y)
+    }
+
+    fn get_asset_data(&mut self) -> AssetData {
+        AssetData {
+            uv: Some(Rect::new(0, 0, render::text_width(&self.text()), render::FONT_SIZE.1)),
+            origin: (0, 0),
+            resource_location: ResourceLocation::new("memory_game", "empty.png"),
+        }
+    }
+
+    fn set_asset_data(&mut self, ass: AssetData) {}
+
+    fn get_resource_location(&mut self) -> ResourceLocation {
+        ResourceLocation::new("game", "widgets/board_size")
+    }
+
+    fn get_allignment(&mut self) -> Alignment {
+        self.alignment.clone()
+    }
+
+    fn set_allignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment
+    }
+
+    fn get_game(&mut self) {
+        self.game;
+    }
+
+    fn return_enum_int(&mut self) -> Option<usize> {
+        let (options, current) = self.options();
+        options.iter().position(|p| *p == current)
+    }
+
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
+        let text = self.text();
+        let coords = self.correct_coords();
+        if debug {
+            render::draw_pp_texture(coords.0, coords.1, &self.get_debug_asset_data(), canvas, sf, textures)
+        }
+        // lift the text when hovered, like the cards in a match
+        let lift = if self.selected {2} else {0};
+        render::draw_text(coords.0, coords.1 - lift, &text, canvas, sf, textures);
+    }
+}
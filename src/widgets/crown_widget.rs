@@ -1,22 +1,28 @@
 use std::collections::HashMap;
 use sdl2::keyboard::Keycode::N;
 use sdl2::rect::Rect;
-use crate::game::{Game, Turn};
+use crate::animation::AnimatedAssetData;
+use crate::game::Game;
 use crate::render::AssetData;
 use crate::resource_location::ResourceLocation;
 use crate::screen::Screen;
 use crate::screens::hud_screen::HudScreen;
 use crate::widget::Alignment;
 use crate::widget::Widget;
+use crate::tween::{Easing, Tween};
 
+// how long the crown takes to drop onto the winner, and how far above it starts
+const DROP_TIME : f32 = 0.7;
+const DROP_HEIGHT : f32 = 40.0;
 
 pub struct CrownWidget {
-    asset_data: AssetData,
+    asset_data: AnimatedAssetData, // shines now and then once it is on the winner
     alignment: Alignment,
     coords : (i32, i32),
     game : *mut Game,
-    player : Turn,
-    selected : bool
+    player : usize,
+    selected : bool,
+    drop : Option<Tween<f32>> // how far above its place the crown is, None until the player wins
 }
 
 impl CrownWidget {
@@ -32,22 +38,23 @@ impl CrownWidget {
     }
      */
 
-    pub fn create(alignment: Alignment, x : i32, y : i32, game : *mut Game, player : Turn) -> Box<Self>
+    pub fn create(alignment: Alignment, x : i32, y : i32, game : *mut Game, player : usize) -> Box<Self>
     where
         Self: Sized
     {
 
         let ret = Self {
-            asset_data: AssetData {
+            asset_data: AnimatedAssetData::create(Some(ResourceLocation::new("memory_game", "animations/crown.json")), AssetData {
                 uv: Some(Rect::new(0, 0, 22, 20)),
                 origin: (11, 10),
                 resource_location: ResourceLocation::new("memory_game", "gui/crown.png"),
-            },
+            }),
             alignment,
             coords: (x, y),
             game,
             player,
             selected: false,
+            drop: None,
         };
         Box::new(ret)
     }
@@ -67,7 +74,8 @@ impl Widget for CrownWidget {
     }
 
     fn get_screen_coordinates(&mut self) -> (i32, i32) {
-        self.coords.clone()
+        let height = self.drop.as_ref().map(|drop| drop.value()).unwrap_or(0.0);
+        (self.coords.0, self.coords.1 + height as i32)
     }
 
     fn set_screen_coordinates(&mut self, x: i32, y: i32) {
@@ -85,30 +93,9 @@ impl Widget for CrownWidget {
             game.use_finger=false;
         }
 
-        if game.get_player_1_score() + game.get_player_2_score() == 9 {
-            match self.player {
-                Turn::Player1 => {
-                    if game.get_player_1_score() > game.get_player_2_score() {
-                        self.asset_data.clone()
-                    }
-                    else {
-                        let mut ass = AssetData::empty();
-                        ass.resource_location = ResourceLocation::new("memory_game", "empty.png");
-                        ass
-                    }
-                }
-                Turn::Player2 => {
-                    if game.get_player_2_score() > game.get_player_1_score() {
-                        self.asset_data.clone()
-                    }
-                    else {
-                        let mut ass = AssetData::empty();
-                        ass.resource_location = ResourceLocation::new("memory_game", "empty.png");
-                        ass
-                    }
-                }
-            }
-
+        // only an outright winner gets the crown, nobody does on a draw
+        if game.is_game_over() && game.rules.winner() == Some(self.player) {
+            self.asset_data.current(&game.assets.animations)
         }
         else {
             let mut ass = AssetData::empty();
@@ -118,7 +105,7 @@ impl Widget for CrownWidget {
     }
 
     fn set_asset_data(&mut self, ass: AssetData) {
-        self.asset_data = ass
+        self.asset_data.set_still(ass)
     }
 
     fn get_resource_location(&mut self) -> ResourceLocation {
@@ -138,5 +125,24 @@ impl Widget for CrownWidget {
         self.game;
     }
 
+    fn tick(&mut self, delta: f32) {
+        let game = unsafe { &mut *self.game };
+        let won = game.is_game_over() && game.rules.winner() == Some(self.player);
+        if !won {
+            self.drop = None;
+            self.asset_data.set_elapsed(0.0);
+            return
+        }
+        self.asset_data.tick(delta);
+        // drop the crown onto the winner, with a pop as it lands
+        let game = self.game;
+        self.drop
+            .get_or_insert_with(|| {
+                Tween::create(DROP_HEIGHT, 0.0, DROP_TIME, Easing::Bounce)
+                    .on_complete(move || unsafe { &mut *game }.play_sound(ResourceLocation::new("memory_game", "sounds/pop.ogg")))
+            })
+            .tick(delta);
+    }
+
 
 }
\ No newline at end of file
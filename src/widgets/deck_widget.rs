@@ -0,0 +1,188 @@
+use crate::assets::Registry;
+use sdl2::rect::Rect;
+use sdl2::render::{Texture, WindowCanvas};
+use crate::animation::AnimatedAssetData;
+use crate::game::Game;
+use crate::render;
+use crate::render::AssetData;
+use crate::resource_location::ResourceLocation;
+use crate::widget::{Alignment, Widget};
+
+// how many card faces are shown in the preview, and how far apart they are
+const PREVIEW_CARDS : usize = 3;
+const PREVIEW_STEP : i32 = 30;
+
+/// Cycles through the loaded decks when clicked, like an [`EnumWidget`], and shows a preview of a few cards from the chosen one
+///
+/// [`EnumWidget`]: crate::widgets::enum_widget::EnumWidget
+pub struct DeckWidget {
+    current_indx : usize,
+    selected : bool,
+    alignment: Alignment,
+    coords : (i32, i32),
+    back : AnimatedAssetData, // the chosen deck's card back, which can be a texture or an animation
+    game : *mut Game
+}
+
+impl DeckWidget {
+    pub fn create(alignment: Alignment, x : i32, y : i32, game : *mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let mut ret = Self {
+            current_indx: 0,
+            selected: false,
+            alignment,
+            coords: (x, y),
+            back: AnimatedAssetData::create(None, AssetData {
+                uv: Some(Rect::new(0, 0, 45, 68)),
+                origin: (0, 0),
+                resource_location: ResourceLocation::new("memory_game", "cards/card_reverse.png"),
+            }),
+            game
+        };
+
+        // start on the deck that is already chosen, if there is one
+        let keys = ret.deck_keys();
+        let chosen = unsafe { (*game).deck.clone() };
+        if let Some(indx) = chosen.and_then(|rl| keys.iter().position(|k| *k == rl)) {
+            ret.current_indx = indx;
+        }
+        ret.apply();
+        Box::new(ret)
+    }
+
+    // the decks in a stable order
+    fn deck_keys(&mut self) -> Vec<String> {
+        let game = unsafe { &mut *self.game };
+        game.assets.decks.keys().iter().map(|rl| rl.to_string()).collect()
+    }
+
+    // write the current choice to the game, resetting the board size to the deck's default
+    fn apply(&mut self) {
+        let keys = self.deck_keys();
+        let game = unsafe { &mut *self.game };
+        game.deck = keys.get(self.current_indx).cloned();
+        game.pair_count = None;
+    }
+
+    /// The width and height of the widget
+    fn size() -> (u32, u32) {
+        (45 + (PREVIEW_STEP * PREVIEW_CARDS as i32) as u32 + 40, 68 + render::FONT_SIZE.1 + 6)
+    }
+}
+
+impl Widget for DeckWidget {
+
+    // Cycle through the decks when clicked
+    fn on_click(&mut self) {
+        let count = self.deck_keys().len();
+        if count == 0 {
+            return
+        }
+        if self.current_indx >= count - 1 {
+            self.current_indx = 0;
+        }
+        else {
+            self.current_indx += 1;
+        }
+        self.apply();
+        unsafe { (*self.game).play_sound(ResourceLocation::new("memory_game", "sounds/flip.ogg")) };
+    }
+
+    fn get_selected(&mut self) -> bool {
+        self.selected
+    }
+
+    fn set_selected(&mut self, tf: bool) {
+        self.selected = tf
+    }
+
+    fn get_screen_coordinates(&mut self) -> (i32, i32) {
+        self.coords
+    }
+
+    fn set_screen_coordinates(&mut self, x: i32, y: i32) {
+        self.coords = (x, y)
+    }
+
+    fn get_asset_data(&mut self) -> AssetData {
+        // the whole preview area can be clicked
+        let size = Self::size();
+        AssetData {
+            uv: Some(Rect::new(0, 0, size.0, size.1)),
+            origin: (0, 0),
+            resource_location: ResourceLocation::new("memory_game", "empty.png"),
+        }
+    }
+
+    fn set_asset_data(&mut self, ass: AssetData) {}
+
+    fn get_resource_location(&mut self) -> ResourceLocation {
+        ResourceLocation::new("game", "widgets/deck")
+    }
+
+    fn get_allignment(&mut self) -> Alignment {
+        self.alignment.clone()
+    }
+
+    fn set_allignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment
+    }
+
+    fn get_game(&mut self) {
+        self.game;
+    }
+
+    fn return_enum_int(&mut self) -> Option<usize> {
+        Some(self.current_indx)
+    }
+
+    fn tick(&mut self, delta: f32) {
+        self.back.tick(delta);
+    }
+
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
+        let game = unsafe { &mut *self.game };
+        let coords = self.correct_coords();
+        let size = Self::size();
+        if debug {
+            render::draw_pp_texture(coords.0, coords.1, &self.get_debug_asset_data(), canvas, sf, textures)
+        }
+
+        let deck = match game.deck.as_ref().and_then(|rl| game.assets.decks.find(rl)) {
+            Some(deck) => {deck.clone()}
+            None => {
+                render::draw_text(coords.0, coords.1, "NO DECKS", canvas, sf, textures);
+                return
+            }
+        };
+
+        // deck name, with arrows to show it can be cycled
+        let name = format!("< {} >", deck.get_name());
+        render::draw_text(coords.0 + (size.0 as i32 - render::text_width(&name) as i32) / 2, coords.1, &name, canvas, sf, textures);
+
+        // the card back, followed by a few faces fanned out on top of it. The preview lifts when hovered, like the cards in a match
+        let lift = if self.selected {4} else {0};
+        let top = coords.1 + render::FONT_SIZE.1 as i32 + 6 - lift;
+        let left = coords.0 + 20;
+        let card_back = deck.get_card_back();
+        if game.assets.animations.get(&card_back).is_some() {
+            self.back.set_animation(Some(card_back));
+        }
+        else {
+            let mut still = self.back.get_still().clone();
+            still.resource_location = card_back;
+            self.back.set_still(still);
+            self.back.set_animation(None);
+        }
+        let mut ass = self.back.current(&game.assets.animations);
+        // the preview is placed by its top left corner, not the animation's origin
+        ass.origin = (0, 0);
+        render::draw_pp_texture(left, top, &ass, canvas, sf, textures);
+        for (i, card) in deck.pool(&game.assets.cards).iter().take(PREVIEW_CARDS).enumerate() {
+            ass.resource_location = card.get_texture_location();
+            render::draw_pp_texture(left + PREVIEW_STEP * (i as i32 + 1), top, &ass, canvas, sf, textures);
+        }
+    }
+}
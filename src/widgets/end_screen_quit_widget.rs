@@ -9,6 +9,11 @@ use crate::screen::Screen;
 use crate::screens::hud_screen::HudScreen;
 use crate::widget::Alignment;
 use crate::widget::Widget;
+use crate::tween::{Appear, Easing};
+
+// how long the button takes to slide into place, and how far below it starts
+const APPEAR_TIME : f32 = 0.4;
+const APPEAR_DISTANCE : f32 = 30.0;
 
 pub struct EndQuitWidget {
     selected : bool,
@@ -16,6 +21,7 @@ pub struct EndQuitWidget {
     asset_data_selected : AssetData,
     alignment: Alignment,
     coords : (i32, i32),
+    appear : Appear, // slides the button up into place once the match is over
     game : *mut Game
 }
 
@@ -51,6 +57,7 @@ impl EndQuitWidget {
             },
             alignment,
             coords: (x, y),
+            appear: Appear::create(APPEAR_TIME, Easing::Back),
             game
         };
         Box::new(ret)
@@ -61,10 +68,9 @@ impl Widget for EndQuitWidget {
     fn on_click(&mut self) {
         // only run if game over
         let game = unsafe { &mut *self.game };
-        if game.get_player_1_score() + game.get_player_2_score() == 9 {
-            info!("Quitting");
-            game.running = false
-            //(*self.game).unwrap().current_screen = None;
+        if game.is_game_over() {
+            info!("Back to the main menu");
+            game.return_to_menu()
         }
     }
 
@@ -77,7 +83,7 @@ impl Widget for EndQuitWidget {
     }
 
     fn get_screen_coordinates(&mut self) -> (i32, i32) {
-        self.coords.clone()
+        (self.coords.0, self.coords.1 - ((1.0 - self.appear.value()) * APPEAR_DISTANCE) as i32)
     }
 
     fn set_screen_coordinates(&mut self, x: i32, y: i32) {
@@ -88,12 +94,12 @@ impl Widget for EndQuitWidget {
 
         // dont show finger if game is not over
         let game = unsafe { &mut *self.game };
-        if (game.get_player_1_score() + game.get_player_2_score() != 9) && self.selected{
+        if !game.is_game_over() && self.selected{
             game.use_finger=false;
         }
 
         // ger asset data
-        if game.get_player_1_score() + game.get_player_2_score() == 9 {
+        if game.is_game_over() {
             if self.selected {
                 self.asset_data_selected.clone()
             } else {
@@ -129,5 +135,14 @@ impl Widget for EndQuitWidget {
         self.game;
     }
 
+    fn tick(&mut self, delta: f32) {
+        let game = unsafe { &mut *self.game };
+        self.appear.update(game.is_game_over(), delta);
+    }
+
+    fn get_alpha(&mut self) -> u8 {
+        self.appear.alpha()
+    }
+
 
 }
\ No newline at end of file
@@ -1,7 +1,10 @@
+use crate::assets::Registry;
 use log::warn;
 use sdl2::keyboard::Keycode::N;
 use sdl2::rect::Rect;
+use sdl2::render::{Texture, WindowCanvas};
 use crate::game::Game;
+use crate::render;
 use crate::render::AssetData;
 use crate::resource_location::ResourceLocation;
 use crate::widget::{Alignment, Widget};
@@ -10,6 +13,7 @@ use crate::widgets::err_widget::ErrWidget;
 pub struct EnumWidget<T : WidgetEnum> {
     enum_type : T,
     current_indx : usize,
+    label : Option<String>, // if set, the widget is drawn as text instead of using a texture for each value
     selected : bool,
     alignment: Alignment,
     coords : (i32, i32),
@@ -25,9 +29,19 @@ impl<T : WidgetEnum + 'static + Clone> EnumWidget<T> {
     }
 
     pub fn create(alignment: Alignment, x: i32, y: i32, game: *mut Game, enumt : T) -> Box<dyn Widget> {
+        Self::build(alignment, x, y, game, enumt, None)
+    }
+
+    /// Create an enum widget drawn as a line of text, like "VOLUME < 50% >", for enums that don't have textures for their values
+    pub fn create_labelled(alignment: Alignment, x: i32, y: i32, game: *mut Game, enumt : T, label : &str) -> Box<dyn Widget> {
+        Self::build(alignment, x, y, game, enumt, Some(label.to_string()))
+    }
+
+    fn build(alignment: Alignment, x: i32, y: i32, game: *mut Game, mut enumt : T, label : Option<String>) -> Box<dyn Widget> {
         let ret = Self {
+            current_indx: enumt.get_index(),
             enum_type:enumt ,
-            current_indx: 0,
+            label,
             selected: false,
             asset_data: AssetData {
                 uv: Some(Rect::new(0, 0, 20, 20)),
@@ -48,6 +62,15 @@ impl<T : WidgetEnum + 'static + Clone> EnumWidget<T> {
     }
 }
 
+impl<T : WidgetEnum> EnumWidget<T> {
+
+    // the text shown for labelled widgets
+    fn text(&mut self) -> String {
+        let value = self.enum_type.get_as_string().replace('_', " ").to_uppercase();
+        format!("{} < {} >", self.label.clone().unwrap_or_default(), value)
+    }
+}
+
 impl<T : WidgetEnum> Widget for EnumWidget<T> {
 
     // Cycle through the enum values when clicked
@@ -59,6 +82,7 @@ impl<T : WidgetEnum> Widget for EnumWidget<T> {
             self.current_indx +=1;
         }
         self.enum_type = T::get_from_index(self.current_indx);
+        unsafe { (*self.game).play_sound(ResourceLocation::new("memory_game", "sounds/pop.ogg")) };
     }
 
     fn get_selected(&mut self) -> bool {
@@ -78,6 +102,15 @@ impl<T : WidgetEnum> Widget for EnumWidget<T> {
     }
     fn get_asset_data(&mut self) -> AssetData {
 
+        // labelled widgets are the size of their text
+        if self.label.is_some() {
+            return AssetData {
+                uv: Some(Rect::new(0, 0, render::text_width(&self.text()), render::FONT_SIZE.1)),
+                origin: (0, 0),
+                resource_location: ResourceLocation::new("memory_game", "empty.png"),
+            }
+        }
+
         // using the name of the enums different values to get the textures allows dynamic generation of the resource location
         let rl = ResourceLocation::new("game", format!("gui/widgets/enum/{}/{}.png", self.enum_type.name(), self.enum_type.get_as_string()).as_str());
         self.asset_data.resource_location = rl.clone();
@@ -115,6 +148,21 @@ impl<T : WidgetEnum> Widget for EnumWidget<T> {
         Some(self.current_indx)
     }
 
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
+        let coords = self.correct_coords();
+        if debug {
+            render::draw_pp_texture(coords.0, coords.1, &self.get_debug_asset_data(), canvas, sf, textures)
+        }
+        if self.label.is_some() {
+            // lift the text when hovered, like the cards in a match
+            let lift = if self.selected {2} else {0};
+            render::draw_text(coords.0, coords.1 - lift, &self.text(), canvas, sf, textures);
+        }
+        else {
+            render::draw_pp_texture(coords.0, coords.1, &self.get_asset_data(), canvas, sf, textures)
+        }
+    }
+
 }
 
 pub trait WidgetEnum {
@@ -125,6 +173,9 @@ pub trait WidgetEnum {
     // get a enum value from an integer
     fn get_from_index(index : usize) -> Self;
 
+    // the integer for this enum value, the opposite of get_from_index
+    fn get_index(&mut self) -> usize;
+
     // how many enum values there are in the enum
     fn count(&mut self) -> usize;
 
@@ -1,5 +1,4 @@
 use std::collections::HashMap;
-use log::{error, warn};
 use sdl2::rect::Rect;
 use crate::game::Game;
 use crate::render::AssetData;
@@ -7,6 +6,7 @@ use crate::resource_location::ResourceLocation;
 use crate::widget::Alignment;
 use crate::widget::Widget;
 
+/// A warning icon shown next to errors. Clicking it closes the screen it is on
 pub struct ErrWidget {
     selected : bool,
     asset_data: AssetData,
@@ -67,8 +67,14 @@ impl ErrWidget {
 }
 
 impl Widget for ErrWidget {
+    // Dismiss the screen showing the error
     fn on_click(&mut self) {
-        error!("you flipped up buddy");
+        if let Some(game) = self.game {
+            unsafe {
+                (*game).play_sound(ResourceLocation::new("memory_game", "sounds/pop.ogg"));
+                (*game).pop_screen();
+            }
+        }
     }
 
     fn get_selected(&mut self) -> bool {
@@ -0,0 +1,97 @@
+use crate::assets::Registry;
+use sdl2::rect::Rect;
+use sdl2::render::{Texture, WindowCanvas};
+use crate::game::Game;
+use crate::render;
+use crate::render::AssetData;
+use crate::resource_location::ResourceLocation;
+use crate::widget::{Alignment, Widget};
+
+/// A line of text that can't be clicked, used for titles and descriptions
+pub struct LabelWidget {
+    text : String,
+    selected : bool,
+    alignment: Alignment,
+    coords : (i32, i32),
+    game : *mut Game
+}
+
+impl LabelWidget {
+    pub fn create(alignment: Alignment, x : i32, y : i32, text : &str, game : *mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let ret = Self {
+            text: text.to_string(),
+            selected: false,
+            alignment,
+            coords: (x, y),
+            game
+        };
+        Box::new(ret)
+    }
+
+    /// Change the text shown
+    pub fn set_text(&mut self, text : &str) {
+        self.text = text.to_string()
+    }
+}
+
+impl Widget for LabelWidget {
+    fn on_click(&mut self) {}
+
+    fn get_selected(&mut self) -> bool {
+        self.selected
+    }
+
+    fn set_selected(&mut self, tf: bool) {
+        self.selected = tf
+    }
+
+    fn get_screen_coordinates(&mut self) -> (i32, i32) {
+        self.coords
+    }
+
+    fn set_screen_coordinates(&mut self, x: i32, y: i32) {
+        self.coords = (x, y)
+    }
+
+    fn get_asset_data(&mut self) -> AssetData {
+        // the size of the text, so the debug background covers it
+        AssetData {
+            uv: Some(Rect::new(0, 0, render::text_width(&self.text).max(1), render::FONT_SIZE.1)),
+            origin: (0, 0),
+            resource_location: ResourceLocation::new("memory_game", "empty.png"),
+        }
+    }
+
+    fn set_asset_data(&mut self, ass: AssetData) {}
+
+    fn get_resource_location(&mut self) -> ResourceLocation {
+        ResourceLocation::new("game", "widgets/label")
+    }
+
+    fn get_allignment(&mut self) -> Alignment {
+        self.alignment.clone()
+    }
+
+    fn set_allignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment
+    }
+
+    fn get_game(&mut self) {
+        self.game;
+    }
+
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
+        // labels can't be clicked, so don't show the finger
+        if self.selected {
+            unsafe { (*self.game).use_finger = false; }
+        }
+        let coords = self.correct_coords();
+        if debug {
+            render::draw_pp_texture(coords.0, coords.1, &self.get_debug_asset_data(), canvas, sf, textures)
+        }
+        render::draw_text(coords.0, coords.1, &self.text, canvas, sf, textures);
+    }
+}
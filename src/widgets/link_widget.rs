@@ -0,0 +1,137 @@
+use crate::assets::Registry;
+use sdl2::rect::Rect;
+use sdl2::render::{Texture, WindowCanvas};
+use crate::game::Game;
+use crate::render;
+use crate::render::AssetData;
+use crate::resource_location::ResourceLocation;
+use crate::screen::Screen;
+use crate::screens::packs_screen::PacksScreen;
+use crate::screens::settings_screen::SettingsScreen;
+use crate::screens::stats_screen::StatsScreen;
+use crate::widget::{Alignment, Widget};
+
+/// Where a link takes the player
+pub enum Link {
+    /// Back to the screen below this one
+    Back,
+    /// Back to the main menu, leaving any match
+    MainMenu,
+    /// The saved statistics
+    Stats,
+    /// Straight back into the saved match
+    Continue,
+    /// Carry on with the paused match
+    Resume,
+    /// Deal the paused match again
+    Restart,
+    /// The settings screen
+    Settings,
+    /// The resource packs screen
+    Packs
+}
+
+/// A line of text that opens another screen when clicked, for menu entries that don't have their own button texture
+pub struct LinkWidget {
+    text : String,
+    target : Link,
+    selected : bool,
+    alignment: Alignment,
+    coords : (i32, i32),
+    game : *mut Game
+}
+
+impl LinkWidget {
+    pub fn create(alignment: Alignment, x : i32, y : i32, text : &str, target : Link, game : *mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let ret = Self {
+            text: text.to_string(),
+            target,
+            selected: false,
+            alignment,
+            coords: (x, y),
+            game
+        };
+        Box::new(ret)
+    }
+}
+
+impl Widget for LinkWidget {
+    fn on_click(&mut self) {
+        let game = unsafe { &mut *self.game };
+        game.play_sound(ResourceLocation::new("memory_game", "sounds/pop.ogg"));
+        match self.target {
+            Link::Back => {game.pop_screen()}
+            Link::MainMenu => {game.return_to_menu()}
+            Link::Stats => {
+                let screen = StatsScreen::create(game);
+                game.push_screen(screen)
+            }
+            Link::Continue => {game.continue_match()}
+            Link::Resume => {game.resume()}
+            Link::Restart => {game.restart()}
+            Link::Settings => {
+                let screen = SettingsScreen::create(game);
+                game.push_screen(screen)
+            }
+            Link::Packs => {
+                let screen = PacksScreen::create(game);
+                game.push_screen(screen)
+            }
+        }
+    }
+
+    fn get_selected(&mut self) -> bool {
+        self.selected
+    }
+
+    fn set_selected(&mut self, tf: bool) {
+        self.selected = tf
+    }
+
+    fn get_screen_coordinates(&mut self) -> (i32, i32) {
+        self.coords
+    }
+
+    fn set_screen_coordinates(&mut self, x: i32, y: i32) {
+        self.coords = (x, y)
+    }
+
+    fn get_asset_data(&mut self) -> AssetData {
+        AssetData {
+            uv: Some(Rect::new(0, 0, render::text_width(&self.text).max(1), render::FONT_SIZE.1)),
+            origin: (0, 0),
+            resource_location: ResourceLocation::new("memory_game", "empty.png"),
+        }
+    }
+
+    fn set_asset_data(&mut self, ass: AssetData) {}
+
+    fn get_resource_location(&mut self) -> ResourceLocation {
+        ResourceLocation::new("game", "widgets/link")
+    }
+
+    fn get_allignment(&mut self) -> Alignment {
+        self.alignment.clone()
+    }
+
+    fn set_allignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment
+    }
+
+    fn get_game(&mut self) {
+        self.game;
+    }
+
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
+        let coords = self.correct_coords();
+        if debug {
+            render::draw_pp_texture(coords.0, coords.1, &self.get_debug_asset_data(), canvas, sf, textures)
+        }
+        // lift the text when hovered, like the cards in a match
+        let lift = if self.selected {2} else {0};
+        render::draw_text(coords.0, coords.1 - lift, &self.text, canvas, sf, textures);
+    }
+}
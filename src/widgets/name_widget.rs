@@ -0,0 +1,170 @@
+use crate::assets::Registry;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::rect::Rect;
+use sdl2::render::{Texture, WindowCanvas};
+use crate::game::Game;
+use crate::render;
+use crate::render::AssetData;
+use crate::resource_location::ResourceLocation;
+use crate::widget::{Alignment, Widget};
+
+/// The longest name that can be typed
+pub const NAME_LENGTH : usize = 12;
+
+/// The name a player starts a match with, typed in after clicking it. Enter or clicking anywhere keeps the new name, Escape puts the old one back.
+/// An empty name goes back to "Player N"
+pub struct NameWidget {
+    player : usize, // counted from 0
+    typed : Option<String>, // the name so far while it is being typed
+    selected : bool,
+    alignment: Alignment,
+    coords : (i32, i32),
+    game : *mut Game
+}
+
+impl NameWidget {
+    pub fn create(alignment: Alignment, x : i32, y : i32, player : usize, game : *mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let ret = Self {
+            player,
+            typed: None,
+            selected: false,
+            alignment,
+            coords: (x, y),
+            game
+        };
+        Box::new(ret)
+    }
+
+    // the text shown, with a cursor at the end while typing
+    fn text(&mut self) -> String {
+        let game = unsafe { &mut *self.game };
+        let name = match &self.typed {
+            Some(typed) => {format!("{}_", typed)}
+            None => {game.settings.player_name(self.player)}
+        };
+        format!("PLAYER {} NAME [ {} ]", self.player + 1, name)
+    }
+
+    fn start(&mut self) {
+        let game = unsafe { &mut *self.game };
+        self.typed = Some(game.settings.player_names.get(self.player).cloned().unwrap_or_default());
+        game.typing = true;
+    }
+
+    // stop typing, saving the name unless it was cancelled
+    fn finish(&mut self, keep : bool) {
+        let game = unsafe { &mut *self.game };
+        game.typing = false;
+        let typed = match self.typed.take() {
+            Some(typed) if keep => {typed.trim().to_string()}
+            _ => {return}
+        };
+        let names = &mut game.settings.player_names;
+        if names.get(self.player) == Some(&typed) || (typed.is_empty() && names.len() <= self.player) {
+            return
+        }
+        if names.len() <= self.player {
+            names.resize(self.player + 1, String::new());
+        }
+        names[self.player] = typed;
+        // names left empty at the end aren't worth keeping
+        while names.last().is_some_and(|name| name.is_empty()) {
+            names.pop();
+        }
+        game.settings.save();
+    }
+}
+
+impl Widget for NameWidget {
+
+    // start typing, or keep what has been typed if already typing
+    fn on_click(&mut self) {
+        if self.typed.is_some() {
+            self.finish(true);
+        }
+        else {
+            self.start();
+        }
+        unsafe { (*self.game).play_sound(ResourceLocation::new("memory_game", "sounds/pop.ogg")) };
+    }
+
+    fn on_event(&mut self, event : &Event) {
+        let typed = match &mut self.typed {
+            Some(typed) => {typed}
+            None => {return}
+        };
+        match event {
+            Event::TextInput { text, .. } => {
+                // only what the font can draw
+                for character in text.chars().filter(|c| (' '..='~').contains(c)) {
+                    if typed.chars().count() < NAME_LENGTH {
+                        typed.push(character);
+                    }
+                }
+            }
+            Event::KeyDown { keycode: Some(Keycode::Backspace), .. } => {typed.pop();}
+            Event::KeyDown { keycode: Some(Keycode::Return), .. } | Event::KeyDown { keycode: Some(Keycode::KpEnter), .. } => {self.finish(true)}
+            Event::KeyDown { keycode: Some(Keycode::Escape), .. } => {self.finish(false)}
+            // clicking somewhere else keeps the name, clicking the widget itself is handled by on_click
+            Event::MouseButtonDown { .. } if !self.selected => {self.finish(true)}
+            _ => {}
+        }
+    }
+
+    fn get_selected(&mut self) -> bool {
+        self.selected
+    }
+
+    fn set_selected(&mut self, tf: bool) {
+        self.selected = tf
+    }
+
+    fn get_screen_coordinates(&mut self) -> (i32, i32) {
+        self.coords
+    }
+
+    fn set_screen_coordinates(&mut self, x: i32, y: i32) {
+        self.coords = (x, y)
+    }
+
+    fn get_asset_data(&mut self) -> AssetData {
+        // the size of the text, so all of it can be clicked
+        AssetData {
+            uv: Some(Rect::new(0, 0, render::text_width(&self.text()), render::FONT_SIZE.1)),
+            origin: (0, 0),
+            resource_location: ResourceLocation::new("memory_game", "empty.png"),
+        }
+    }
+
+    fn set_asset_data(&mut self, _ass: AssetData) {}
+
+    fn get_resource_location(&mut self) -> ResourceLocation {
+        ResourceLocation::new("game", "widgets/name")
+    }
+
+    fn get_allignment(&mut self) -> Alignment {
+        self.alignment.clone()
+    }
+
+    fn set_allignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment
+    }
+
+    fn get_game(&mut self) {
+        self.game;
+    }
+
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
+        let coords = self.correct_coords();
+        if debug {
+            render::draw_pp_texture(coords.0, coords.1, &self.get_debug_asset_data(), canvas, sf, textures)
+        }
+        // lift the text when hovered or being typed in, like the other options
+        let lift = if self.selected || self.typed.is_some() {2} else {0};
+        render::draw_text(coords.0, coords.1 - lift, &self.text(), canvas, sf, textures);
+    }
+}
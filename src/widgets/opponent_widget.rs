@@ -0,0 +1,131 @@
+use crate::assets::Registry;
+use sdl2::rect::Rect;
+use sdl2::render::{Texture, WindowCanvas};
+use crate::ai::Difficulty;
+use crate::game::Game;
+use crate::render;
+use crate::render::AssetData;
+use crate::resource_location::ResourceLocation;
+use crate::widget::{Alignment, Widget};
+
+/// Cycles between playing against people and the computer at each difficulty when clicked, like an [`EnumWidget`]
+///
+/// [`EnumWidget`]: crate::widgets::enum_widget::EnumWidget
+pub struct OpponentWidget {
+    selected : bool,
+    alignment: Alignment,
+    coords : (i32, i32),
+    game : *mut Game
+}
+
+impl OpponentWidget {
+    pub fn create(alignment: Alignment, x : i32, y : i32, game : *mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let ret = Self {
+            selected: false,
+            alignment,
+            coords: (x, y),
+            game
+        };
+        Box::new(ret)
+    }
+
+    fn text(&mut self) -> String {
+        let game = unsafe { &mut *self.game };
+        if game.player_count == 1 {
+            return String::from("NO OPPONENT")
+        }
+        match game.computer {
+            Some(difficulty) => {format!("< VS CPU ({}) >", difficulty.get_name())}
+            None => {String::from("< VS PEOPLE >")}
+        }
+    }
+}
+
+impl Widget for OpponentWidget {
+
+    // Cycle through people, then each difficulty when clicked
+    fn on_click(&mut self) {
+        let game = unsafe { &mut *self.game };
+        if game.player_count == 1 {
+            return
+        }
+        let all = Difficulty::all();
+        game.computer = match game.computer {
+            None => {Some(all[0])}
+            Some(difficulty) => {
+                let indx = all.iter().position(|d| *d == difficulty).unwrap_or(0);
+                all.get(indx + 1).copied()
+            }
+        };
+        game.play_sound(ResourceLocation::new("memory_game", "sounds/pop.ogg"));
+    }
+
+    fn get_selected(&mut self) -> bool {
+        self.selected
+    }
+
+    fn set_selected(&mut self, tf: bool) {
+        self.selected = tf
+    }
+
+    fn get_screen_coordinates(&mut self) -> (i32, i32) {
+        self.coords
+    }
+
+    fn set_screen_coordinates(&mut self, x: i32, y: i32) {
+        self.coords = (x, y)
+    }
+
+    fn get_asset_data(&mut self) -> AssetData {
+        AssetData {
+            uv: Some(Rect::new(0, 0, render::text_width(&self.text()), render::FONT_SIZE.1)),
+            origin: (0, 0),
+            resource_location: ResourceLocation::new("memory_game", "empty.png"),
+        }
+    }
+
+    fn set_asset_data(&mut self, ass: AssetData) {}
+
+    fn get_resource_location(&mut self) -> ResourceLocation {
+        ResourceLocation::new("game", "widgets/opponent")
+    }
+
+    fn get_allignment(&mut self) -> Alignment {
+        self.alignment.clone()
+    }
+
+    fn set_allignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment
+    }
+
+    fn get_game(&mut self) {
+        self.game;
+    }
+
+    fn return_enum_int(&mut self) -> Option<usize> {
+        let computer = unsafe { (*self.game).computer };
+        match computer {
+            None => {Some(0)}
+            Some(difficulty) => {Difficulty::all().iter().position(|d| *d == difficulty).map(|i| i + 1)}
+        }
+    }
+
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
+        let solo = unsafe { (*self.game).player_count == 1 };
+        // nothing to pick in solo mode, so don't show the finger
+        if solo && self.selected {
+            unsafe { (*self.game).use_finger = false; }
+        }
+        let text = self.text();
+        let coords = self.correct_coords();
+        if debug {
+            render::draw_pp_texture(coords.0, coords.1, &self.get_debug_asset_data(), canvas, sf, textures)
+        }
+        // lift the text when hovered, like the cards in a match
+        let lift = if self.selected && !solo {2} else {0};
+        render::draw_text(coords.0, coords.1 - lift, &text, canvas, sf, textures);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::assets::Registry;
+use sdl2::rect::Rect;
+use sdl2::render::{Texture, WindowCanvas};
+use crate::game::Game;
+use crate::render;
+use crate::render::AssetData;
+use crate::resource_location::ResourceLocation;
+use crate::screen::Screen;
+use crate::screens::packs_screen::PacksScreen;
+use crate::widget::{Alignment, Widget};
+
+/// What clicking a [`PackWidget`] does to its pack
+pub enum PackAction {
+    /// Turn the pack on or off
+    Toggle,
+    /// Load the pack after the one above it, so it wins when both have the same file
+    Raise
+}
+
+/// A line on the packs screen that changes one resource pack. The settings are saved and the assets reloaded straight away
+pub struct PackWidget {
+    name : String,
+    text : String,
+    action : PackAction,
+    selected : bool,
+    alignment: Alignment,
+    coords : (i32, i32),
+    game : *mut Game
+}
+
+impl PackWidget {
+    pub fn create(alignment: Alignment, x : i32, y : i32, name : &str, action : PackAction, game : *mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let enabled = unsafe { &*game }.settings.packs.iter().any(|pack| pack == name);
+        let text = match action {
+            PackAction::Toggle => {format!("[{}] {}", if enabled {"X"} else {" "}, name.to_uppercase())}
+            PackAction::Raise => {String::from("UP")}
+        };
+        let ret = Self {
+            name: name.to_string(),
+            text,
+            action,
+            selected: false,
+            alignment,
+            coords: (x, y),
+            game
+        };
+        Box::new(ret)
+    }
+}
+
+impl Widget for PackWidget {
+    fn on_click(&mut self) {
+        let game = unsafe { &mut *self.game };
+        game.play_sound(ResourceLocation::new("memory_game", "sounds/pop.ogg"));
+
+        // packs are kept lowest priority first, so raising one moves it towards the end
+        let packs = &mut game.settings.packs;
+        let indx = packs.iter().position(|pack| *pack == self.name);
+        match (&self.action, indx) {
+            (PackAction::Toggle, Some(indx)) => {packs.remove(indx);}
+            (PackAction::Toggle, None) => {packs.push(self.name.clone())}
+            (PackAction::Raise, Some(indx)) if indx + 1 < packs.len() => {packs.swap(indx, indx + 1)}
+            _ => {return}
+        }
+        game.settings.save();
+        game.reload_assets = true;
+
+        // build the screen again so the list is in the new order
+        let screen = PacksScreen::create(game);
+        game.replace_screen(screen);
+    }
+
+    fn get_selected(&mut self) -> bool {
+        self.selected
+    }
+
+    fn set_selected(&mut self, tf: bool) {
+        self.selected = tf
+    }
+
+    fn get_screen_coordinates(&mut self) -> (i32, i32) {
+        self.coords
+    }
+
+    fn set_screen_coordinates(&mut self, x: i32, y: i32) {
+        self.coords = (x, y)
+    }
+
+    fn get_asset_data(&mut self) -> AssetData {
+        AssetData {
+            uv: Some(Rect::new(0, 0, render::text_width(&self.text).max(1), render::FONT_SIZE.1)),
+            origin: (0, 0),
+            resource_location: ResourceLocation::new("memory_game", "empty.png"),
+        }
+    }
+
+    fn set_asset_data(&mut self, _ass: AssetData) {}
+
+    fn get_resource_location(&mut self) -> ResourceLocation {
+        ResourceLocation::new("game", "widgets/pack")
+    }
+
+    fn get_allignment(&mut self) -> Alignment {
+        self.alignment.clone()
+    }
+
+    fn set_allignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment
+    }
+
+    fn get_game(&mut self) {
+        self.game;
+    }
+
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
+        let coords = self.correct_coords();
+        if debug {
+            render::draw_pp_texture(coords.0, coords.1, &self.get_debug_asset_data(), canvas, sf, textures)
+        }
+        let lift = if self.selected {2} else {0};
+        render::draw_text(coords.0, coords.1 - lift, &self.text, canvas, sf, textures);
+    }
+}
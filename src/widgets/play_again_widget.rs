@@ -5,10 +5,13 @@ use sdl2::rect::Rect;
 use crate::game::Game;
 use crate::render::AssetData;
 use crate::resource_location::ResourceLocation;
-use crate::screen::Screen;
-use crate::screens::hud_screen::HudScreen;
 use crate::widget::Alignment;
 use crate::widget::Widget;
+use crate::tween::{Appear, Easing};
+
+// how long the button takes to slide into place, and how far below it starts
+const APPEAR_TIME : f32 = 0.4;
+const APPEAR_DISTANCE : f32 = 30.0;
 
 
 // When designing a widget, it is a good idea to keep in mind things like colour and contrast.
@@ -20,6 +23,7 @@ pub struct PlayAgainWidget {
     asset_data_selected : AssetData,
     alignment: Alignment,
     coords : (i32, i32),
+    appear : Appear, // slides the button up into place once the match is over
     game : *mut Game
 }
 
@@ -55,6 +59,7 @@ impl PlayAgainWidget {
             },
             alignment,
             coords: (x, y),
+            appear: Appear::create(APPEAR_TIME, Easing::Back),
             game
         };
         Box::new(ret)
@@ -65,9 +70,8 @@ impl Widget for PlayAgainWidget {
     fn on_click(&mut self) {
         // only run function if the game is over
         let game = unsafe { &mut *self.game };
-        if game.get_player_1_score() + game.get_player_2_score() == 9 {
-            game.create_memory_game_scene();
-            game.current_screen = Some(HudScreen::create(game));
+        if game.is_game_over() {
+            game.restart();
             info!("Resetting and playing again")
             //(*self.game).unwrap().current_screen = None;
         }
@@ -82,7 +86,7 @@ impl Widget for PlayAgainWidget {
     }
 
     fn get_screen_coordinates(&mut self) -> (i32, i32) {
-        self.coords.clone()
+        (self.coords.0, self.coords.1 - ((1.0 - self.appear.value()) * APPEAR_DISTANCE) as i32)
     }
 
     fn set_screen_coordinates(&mut self, x: i32, y: i32) {
@@ -93,12 +97,12 @@ impl Widget for PlayAgainWidget {
         let game = unsafe { &mut *self.game };
 
         // only show finger pointer if the game is over
-        if (game.get_player_1_score() + game.get_player_2_score() != 9) && self.selected{
+        if !game.is_game_over() && self.selected{
             game.use_finger=false;
         }
 
         // get asset data
-        if game.get_player_1_score() + game.get_player_2_score() == 9 {
+        if game.is_game_over() {
             if self.selected {
                 self.asset_data_selected.clone()
             } else {
@@ -134,5 +138,14 @@ impl Widget for PlayAgainWidget {
         self.game;
     }
 
+    fn tick(&mut self, delta: f32) {
+        let game = unsafe { &mut *self.game };
+        self.appear.update(game.is_game_over(), delta);
+    }
+
+    fn get_alpha(&mut self) -> u8 {
+        self.appear.alpha()
+    }
+
 
 }
\ No newline at end of file
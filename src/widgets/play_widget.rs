@@ -5,11 +5,20 @@ use crate::game::Game;
 use crate::render::AssetData;
 use crate::resource_location::ResourceLocation;
 use crate::screen::Screen;
+use crate::screens::deck_select_screen::DeckSelectScreen;
 use crate::screens::hud_screen::HudScreen;
 use crate::widget::Alignment;
 use crate::widget::Widget;
 
 
+/// Where the play button takes the player
+pub enum PlayTarget {
+    /// The screen to pick a deck and board size
+    DeckSelect,
+    /// Straight into a match with the chosen settings
+    Match
+}
+
 // When designing a widget, it is a good idea to keep in mind things like colour and contrast.
 // Some users may have issues looking at certain colours, so keeping a high contrast is important.
 // If you do not want to have high contrast by default, you can add it as a config option
@@ -19,6 +28,7 @@ pub struct PlayWidget {
     asset_data_selected : AssetData,
     alignment: Alignment,
     coords : (i32, i32),
+    target : PlayTarget,
     game : *mut Game
 }
 
@@ -35,7 +45,7 @@ impl PlayWidget {
     }
      */
 
-    pub fn create(alignment: Alignment, x : i32, y : i32, game : *mut Game) -> Box<Self>
+    pub fn create(alignment: Alignment, x : i32, y : i32, target : PlayTarget, game : *mut Game) -> Box<Self>
     where
         Self: Sized
     {
@@ -54,6 +64,7 @@ impl PlayWidget {
             },
             alignment,
             coords: (x, y),
+            target,
             game
         };
         Box::new(ret)
@@ -62,8 +73,19 @@ impl PlayWidget {
 
 impl Widget for PlayWidget {
     fn on_click(&mut self) {
-        unsafe{(*self.game).create_memory_game_scene()}
-        unsafe{(*self.game).current_screen = Some(HudScreen::create(&mut *self.game))}
+        match self.target {
+            PlayTarget::DeckSelect => {
+                let game = unsafe { &mut *self.game };
+                let screen = DeckSelectScreen::create(game);
+                game.push_screen(screen);
+            }
+            PlayTarget::Match => {
+                let game = unsafe { &mut *self.game };
+                game.create_memory_game_scene();
+                let screen = HudScreen::create(game);
+                game.push_screen(screen);
+            }
+        }
         //(*self.game).unwrap().current_screen = None;
     }
 
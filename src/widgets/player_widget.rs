@@ -1,9 +1,9 @@
-use std::collections::HashMap;
+use crate::assets::Registry;
 use std::thread::current;
 use sdl2::render::{Texture, WindowCanvas};
 use sdl2::rect::Rect;
 use crate::entity::Entity;
-use crate::game::{Game, Turn};
+use crate::game::Game;
 use crate::render;
 use crate::render::AssetData;
 use crate::resource_location::ResourceLocation;
@@ -17,7 +17,7 @@ pub struct PlayerWidget {
     alignment: Alignment,
     coords : (i32, i32),
     game : *mut Game,
-    player : Turn,
+    player : usize,
     score : u32
 }
 
@@ -41,48 +41,15 @@ impl Widget for PlayerWidget {
     }
 
     fn get_asset_data(&mut self) -> AssetData {
-        let game = unsafe { &mut *self.game};
-        if game.prev_success {
-            self.asset_data.clone()
-        }
-        else {
-            match self.player {
-                Turn::Player1 => {
-                    if (self.active && game.wait_timer < 0.0) || (game.wait_timer > 0.0) && game.current_turn == Turn::Player2 {
-                        self.asset_data = AssetData {
-                            uv: Some(Rect::new(0, 0, 68, 21)),
-                            origin: (0, 0),
-                            resource_location: ResourceLocation::new("memory_game", "gui/player_1.png"),
-                        };
-                        self.asset_data.clone()
-                    } else {
-                        self.asset_data = AssetData {
-                            uv: Some(Rect::new(0, 21, 68, 21)),
-                            origin: (0, 0),
-                            resource_location: ResourceLocation::new("memory_game", "gui/player_1.png"),
-                        };
-                        self.asset_data.clone()
-                    }
-                }
-                Turn::Player2 => {
-                    if (self.active && game.wait_timer < 0.0) || (game.wait_timer > 0.0) && game.current_turn == Turn::Player1 {
-                        self.asset_data = AssetData {
-                            uv: Some(Rect::new(0, 0, 68, 21)),
-                            origin: (0, 0),
-                            resource_location: ResourceLocation::new("memory_game", "gui/player_2.png"),
-                        };
-                        self.asset_data.clone()
-                    } else {
-                        self.asset_data = AssetData {
-                            uv: Some(Rect::new(0, 21, 68, 21)),
-                            origin: (0, 0),
-                            resource_location: ResourceLocation::new("memory_game", "gui/player_2.png"),
-                        };
-                        self.asset_data.clone()
-                    }
-                }
-            }
-        }
+        // the player whose turn it is gets the highlighted half of the texture
+        let v = if self.active {0} else {21};
+        let rl = ResourceLocation::new("memory_game", &format!("gui/player_{}.png", self.player + 1));
+        self.asset_data = AssetData {
+            uv: Some(Rect::new(0, v, 68, 21)),
+            origin: (0, 0),
+            resource_location: rl,
+        };
+        self.asset_data.clone()
     }
 
     fn set_asset_data(&mut self, ass: AssetData) {
@@ -105,37 +72,55 @@ impl Widget for PlayerWidget {
         todo!()
     }
 
-    fn render(&mut self, textures: &HashMap<String, Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
         let game = unsafe { &mut *self.game};
 
         if self.selected {
             game.use_finger=false;
         }
-        match self.player {
-            Turn::Player1 => {self.score = game.get_player_1_score(); self.active= game.current_turn==Turn::Player1;}
-            Turn::Player2 => {self.score = game.get_player_2_score(); self.active= game.current_turn==Turn::Player2;}
-        }
+        self.score = game.get_score(self.player);
+        self.active = game.get_turn() == self.player;
         let x_y = self.correct_coords();
         if debug {
             render::draw_pp_texture(x_y.0, x_y.1, &self.get_debug_asset_data(), canvas, sf, &textures);
         }
         render::draw_pp_texture(x_y.0, x_y.1, &self.get_asset_data(), canvas, sf, &textures);
-        for x in 0..self.score {
+
+        // in colour blind mode, point at the player whose turn it is as the highlight can be hard to see
+        if self.active && game.settings.colour_blind {
+            render::draw_text(x_y.0 - 8, x_y.1 + 6, ">", canvas, sf, textures);
+        }
+
+        // players that have been given a name show it under their widget
+        let mut top = x_y.1 + 30;
+        if let Some(player) = game.players.get(self.player) {
+            let name = player.get_name();
+            if name != format!("Player {}", self.player + 1) {
+                render::draw_text(x_y.0, x_y.1 + 23, &name.to_uppercase(), canvas, sf, textures);
+                top += render::FONT_SIZE.1 as i32;
+            }
+        }
+
+        // with more than two players the widgets share each side of the board, so the score indicators are laid out in rows instead of one column
+        let per_row = if game.rules.player_count() > 2 {4} else {1};
+        let left = if per_row > 1 {x_y.0 + 2} else {x_y.0 + 26};
+        for x in 0..self.score as i32 {
+            let pos = (left + 16 * (x % per_row), top + 16 * (x / per_row));
             if debug {
-                render::draw_pp_texture(x_y.0+26, 16 * x as i32 + (x_y.1 + 30), {
+                render::draw_pp_texture(pos.0, pos.1, {
                     let mut debug_thing = self.get_debug_asset_data();
                     debug_thing.uv = self.asset_data_score.uv;
                     &debug_thing.clone()
                 }, canvas, sf, textures);
             }
-            render::draw_pp_texture(x_y.0+26, 16 * x as i32 + (x_y.1 + 30), &self.asset_data_score, canvas, sf, textures);
+            render::draw_pp_texture(pos.0, pos.1, &self.asset_data_score, canvas, sf, textures);
         }
 
     }
 }
 
 impl PlayerWidget {
-    pub fn create(alignment: Alignment, x : i32, y : i32, player : Turn ,game : *mut Game) -> Box<Self>
+    pub fn create(alignment: Alignment, x : i32, y : i32, player : usize, game : *mut Game) -> Box<Self>
     where
         Self: Sized
     {
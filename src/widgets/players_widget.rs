@@ -0,0 +1,118 @@
+use crate::assets::Registry;
+use sdl2::rect::Rect;
+use sdl2::render::{Texture, WindowCanvas};
+use crate::game::Game;
+use crate::render;
+use crate::render::AssetData;
+use crate::resource_location::ResourceLocation;
+use crate::widget::{Alignment, Widget};
+
+/// The most players a match can have
+pub const MAX_PLAYERS : usize = 4;
+
+/// Cycles through how many people are playing when clicked, like an [`EnumWidget`]. One player is solo mode
+///
+/// [`EnumWidget`]: crate::widgets::enum_widget::EnumWidget
+pub struct PlayersWidget {
+    selected : bool,
+    alignment: Alignment,
+    coords : (i32, i32),
+    game : *mut Game
+}
+
+impl PlayersWidget {
+    pub fn create(alignment: Alignment, x : i32, y : i32, game : *mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let ret = Self {
+            selected: false,
+            alignment,
+            coords: (x, y),
+            game
+        };
+        Box::new(ret)
+    }
+
+    fn text(&mut self) -> String {
+        let players = unsafe { (*self.game).player_count };
+        if players == 1 {
+            String::from("< SOLO >")
+        }
+        else {
+            format!("< {} PLAYERS >", players)
+        }
+    }
+}
+
+impl Widget for PlayersWidget {
+
+    // Cycle through the player counts when clicked
+    fn on_click(&mut self) {
+        let game = unsafe { &mut *self.game };
+        if game.player_count >= MAX_PLAYERS {
+            game.player_count = 1;
+        }
+        else {
+            game.player_count += 1;
+        }
+        game.play_sound(ResourceLocation::new("memory_game", "sounds/pop.ogg"));
+    }
+
+    fn get_selected(&mut self) -> bool {
+        self.selected
+    }
+
+    fn set_selected(&mut self, tf: bool) {
+        self.selected = tf
+    }
+
+    fn get_screen_coordinates(&mut self) -> (i32, i32) {
+        self.coords
+    }
+
+    fn set_screen_coordinates(&mut self, x: i32, y: i32) {
+        self.coords = (x, y)
+    }
+
+    fn get_asset_data(&mut self) -> AssetData {
+        AssetData {
+            uv: Some(Rect::new(0, 0, render::text_width(&self.text()), render::FONT_SIZE.1)),
+            origin: (0, 0),
+            resource_location: ResourceLocation::new("memory_game", "empty.png"),
+        }
+    }
+
+    fn set_asset_data(&mut self, ass: AssetData) {}
+
+    fn get_resource_location(&mut self) -> ResourceLocation {
+        ResourceLocation::new("game", "widgets/players")
+    }
+
+    fn get_allignment(&mut self) -> Alignment {
+        self.alignment.clone()
+    }
+
+    fn set_allignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment
+    }
+
+    fn get_game(&mut self) {
+        self.game;
+    }
+
+    fn return_enum_int(&mut self) -> Option<usize> {
+        Some(unsafe { (*self.game).player_count } - 1)
+    }
+
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
+        let text = self.text();
+        let coords = self.correct_coords();
+        if debug {
+            render::draw_pp_texture(coords.0, coords.1, &self.get_debug_asset_data(), canvas, sf, textures)
+        }
+        // lift the text when hovered, like the cards in a match
+        let lift = if self.selected {2} else {0};
+        render::draw_text(coords.0, coords.1 - lift, &text, canvas, sf, textures);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::assets::Registry;
+use sdl2::rect::Rect;
+use sdl2::render::{Texture, WindowCanvas};
+use crate::game::Game;
+use crate::render;
+use crate::render::AssetData;
+use crate::resource_location::ResourceLocation;
+use crate::widget::{Alignment, Widget};
+
+/// Shows the final time and flip count once a solo match is over, in place of the crown
+pub struct ResultWidget {
+    selected : bool,
+    alignment: Alignment,
+    coords : (i32, i32),
+    game : *mut Game
+}
+
+impl ResultWidget {
+    pub fn create(alignment: Alignment, x : i32, y : i32, game : *mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let ret = Self {
+            selected: false,
+            alignment,
+            coords: (x, y),
+            game
+        };
+        Box::new(ret)
+    }
+
+    fn lines(&mut self) -> Vec<String> {
+        let game = unsafe { &mut *self.game };
+        vec![
+            String::from("CLEARED!"),
+            format!("TIME  {}S", game.score as u32),
+            format!("FLIPS {}", game.rules.flips()),
+        ]
+    }
+}
+
+impl Widget for ResultWidget {
+    fn on_click(&mut self) {}
+
+    fn get_selected(&mut self) -> bool {
+        self.selected
+    }
+
+    fn set_selected(&mut self, tf: bool) {
+        self.selected = tf
+    }
+
+    fn get_screen_coordinates(&mut self) -> (i32, i32) {
+        self.coords
+    }
+
+    fn set_screen_coordinates(&mut self, x: i32, y: i32) {
+        self.coords = (x, y)
+    }
+
+    fn get_asset_data(&mut self) -> AssetData {
+        let lines = self.lines();
+        let width = lines.iter().map(|l| render::text_width(l)).max().unwrap_or(1);
+        AssetData {
+            uv: Some(Rect::new(0, 0, width, render::FONT_SIZE.1 * lines.len() as u32)),
+            origin: (0, 0),
+            resource_location: ResourceLocation::new("memory_game", "empty.png"),
+        }
+    }
+
+    fn set_asset_data(&mut self, ass: AssetData) {}
+
+    fn get_resource_location(&mut self) -> ResourceLocation {
+        ResourceLocation::new("game", "widgets/result")
+    }
+
+    fn get_allignment(&mut self) -> Alignment {
+        self.alignment.clone()
+    }
+
+    fn set_allignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment
+    }
+
+    fn get_game(&mut self) {
+        self.game;
+    }
+
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
+        let game = unsafe { &mut *self.game };
+
+        // the result can't be clicked, so don't show the finger
+        if self.selected {
+            game.use_finger = false;
+        }
+        // only shown once the match is over
+        if !game.is_game_over() {
+            return
+        }
+        let coords = self.correct_coords();
+        if debug {
+            render::draw_pp_texture(coords.0, coords.1, &self.get_debug_asset_data(), canvas, sf, textures)
+        }
+        for (i, line) in self.lines().iter().enumerate() {
+            render::draw_text(coords.0, coords.1 + (render::FONT_SIZE.1 * i as u32) as i32, line, canvas, sf, textures);
+        }
+    }
+}
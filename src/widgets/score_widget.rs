@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use crate::assets::Registry;
 use num::clamp;
 use sdl2::rect::Rect;
 use sdl2::render::{Texture, WindowCanvas};
@@ -10,17 +10,25 @@ use crate::resource_location::ResourceLocation;
 use crate::widget::{Alignment, Widget};
 use crate::widgets::play_widget::PlayWidget;
 
+/// What number a [`ScoreWidget`] shows
+pub enum Counter {
+    /// Whole seconds since the match started
+    Time,
+    /// How many cards have been flipped this match
+    Flips
+}
+
 pub struct ScoreWidget {
     selected : bool,
     base_asset_data: AssetData,
     alignment: Alignment,
     coords : (i32, i32),
     game : *mut Game,
-    score : u32
+    counter : Counter
 }
 
 impl ScoreWidget {
-    pub fn create(alignment: Alignment, x : i32, y : i32, game : *mut Game) -> Box<Self>
+    pub fn create(alignment: Alignment, x : i32, y : i32, counter : Counter, game : *mut Game) -> Box<Self>
     where
         Self: Sized
     {
@@ -35,7 +43,7 @@ impl ScoreWidget {
             alignment,
             coords: (x, y),
             game,
-            score: 0
+            counter
         };
         Box::new(ret)
     }
@@ -83,9 +91,13 @@ impl Widget for ScoreWidget {
     fn get_game(&mut self) {
     }
 
-    fn render(&mut self, textures: &HashMap<String, Texture>, sf: i32, canvas: &mut WindowCanvas, debug : bool) {
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug : bool) {
         let game = unsafe { &mut *self.game };
-        let score_as_string = format!("{}",game.score.clone() as u32);
+        let score_as_string = match self.counter {
+            Counter::Time => {format!("{}",game.score.clone() as u32)}
+            Counter::Flips => {format!("{}", game.rules.flips())}
+        };
+        let coords = self.correct_coords();
         let mut counter = 0;
         for character in score_as_string.chars() {
             let mut asset_data = self.base_asset_data.clone();
@@ -94,9 +106,9 @@ impl Widget for ScoreWidget {
                 let mut d_ass = asset_data.clone();
                 d_ass.uv = Some(Rect::new(0,0,d_ass.uv.unwrap().width(),d_ass.uv.unwrap().height()));
                 d_ass.resource_location = ResourceLocation::new("game", "gui/widgets/debug_background.png");;
-                render::draw_pp_texture(self.coords.0 + (8 * counter), self.coords.1, &d_ass, canvas, sf, textures);
+                render::draw_pp_texture(coords.0 + (8 * counter), coords.1, &d_ass, canvas, sf, textures);
             }
-            render::draw_pp_texture(self.coords.0 + (8 * counter), self.coords.1, &asset_data, canvas, sf, textures);
+            render::draw_pp_texture(coords.0 + (8 * counter), coords.1, &asset_data, canvas, sf, textures);
             counter+=1;
         }
 
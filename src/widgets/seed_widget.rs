@@ -0,0 +1,102 @@
+use crate::assets::Registry;
+use sdl2::rect::Rect;
+use sdl2::render::{Texture, WindowCanvas};
+use crate::game::Game;
+use crate::render;
+use crate::render::AssetData;
+use crate::resource_location::ResourceLocation;
+use crate::widget::{Alignment, Widget};
+
+/// Shows the seed the match was dealt with once it is over, so the same board can be played again with `--seed`
+pub struct SeedWidget {
+    selected : bool,
+    alignment: Alignment,
+    coords : (i32, i32),
+    game : *mut Game
+}
+
+impl SeedWidget {
+    pub fn create(alignment: Alignment, x : i32, y : i32, game : *mut Game) -> Box<Self>
+    where
+        Self: Sized
+    {
+        let ret = Self {
+            selected: false,
+            alignment,
+            coords: (x, y),
+            game
+        };
+        Box::new(ret)
+    }
+
+    fn text(&mut self) -> String {
+        format!("SEED {}", unsafe { (*self.game).match_seed })
+    }
+}
+
+impl Widget for SeedWidget {
+    fn on_click(&mut self) {}
+
+    fn get_selected(&mut self) -> bool {
+        self.selected
+    }
+
+    fn set_selected(&mut self, tf: bool) {
+        self.selected = tf
+    }
+
+    fn get_screen_coordinates(&mut self) -> (i32, i32) {
+        self.coords
+    }
+
+    fn set_screen_coordinates(&mut self, x: i32, y: i32) {
+        self.coords = (x, y)
+    }
+
+    fn get_asset_data(&mut self) -> AssetData {
+        AssetData {
+            uv: Some(Rect::new(0, 0, render::text_width(&self.text()), render::FONT_SIZE.1)),
+            origin: (0, 0),
+            resource_location: ResourceLocation::new("memory_game", "empty.png"),
+        }
+    }
+
+    fn set_asset_data(&mut self, ass: AssetData) {}
+
+    fn get_resource_location(&mut self) -> ResourceLocation {
+        ResourceLocation::new("game", "widgets/seed")
+    }
+
+    fn get_allignment(&mut self) -> Alignment {
+        self.alignment.clone()
+    }
+
+    fn set_allignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment
+    }
+
+    fn get_game(&mut self) {
+        self.game;
+    }
+
+    fn render(&mut self, textures: &Registry<Texture>, sf: i32, canvas: &mut WindowCanvas, debug: bool) {
+        let game = unsafe { &mut *self.game };
+
+        // the seed can't be clicked, so don't show the finger
+        if self.selected {
+            game.use_finger = false;
+        }
+        // only shown once the match is over
+        if !game.is_game_over() {
+            return
+        }
+        let text = self.text();
+        // centred on the given coordinates, as the seed can be any length
+        let coords = self.correct_coords();
+        let left = coords.0 - render::text_width(&text) as i32 / 2;
+        if debug {
+            render::draw_pp_texture(left, coords.1, &self.get_debug_asset_data(), canvas, sf, textures)
+        }
+        render::draw_text(left, coords.1, &text, canvas, sf, textures);
+    }
+}